@@ -1,13 +1,10 @@
 use std::{thread, time::Duration};
 
 use bloxroute_sdk::{
-    models::{
-        transaction::{BloxrouteBlockRequestInclude, BloxrouteTransactionRequestInclude},
-        BloxrouteResponseEnum,
-    },
+    models::transaction::{BloxrouteBlockRequestInclude, BloxrouteTransactionRequestInclude},
     providers::ws::BloxrouteWsClient,
 };
-use futures_util::future::join_all;
+use futures_util::{future::join_all, StreamExt};
 use tokio::task::JoinHandle;
 
 #[tokio::main]
@@ -16,32 +13,11 @@ async fn main() {
     let auth_header = std::env::var("auth_header").unwrap();
     let timeout = 5000;
     let mut client =
-        BloxrouteWsClient::connect(endpoint.to_string(), auth_header.to_string(), timeout).await;
+        BloxrouteWsClient::connect(endpoint.to_string(), auth_header.to_string(), timeout)
+            .await
+            .unwrap();
 
     let mut thread_handles: Vec<JoinHandle<()>> = vec![];
-    let client_receiver_clone = client.clone();
-    thread_handles.push(tokio::spawn(async move {
-        let receiver_clone = client_receiver_clone.rx.clone();
-        let mut receiver = receiver_clone.lock().await;
-        while let Some(data) = receiver.recv().await {
-            match &data {
-                BloxrouteResponseEnum::Transaction(_) => println!("New tx"),
-                BloxrouteResponseEnum::Subscription(sub) => println!("{:#?}", sub),
-                BloxrouteResponseEnum::Block(_) => println!("New block"),
-                BloxrouteResponseEnum::OpenbookGetMarkets(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::OpenbookGetTickers(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::OpenbookGetOrderbookResponse(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::OpenbookGetDepth(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::GetBundleTipStream(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::GetStreamPriorityFee(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::RaydiumStreamReservesResponse(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::RaydiumStreamSwapsResponse(x) => println!("{:#?}", x),
-                BloxrouteResponseEnum::RaydiumNewRaydiumPoolsResponse(x) => {
-                    println!("{:#?}", x)
-                }
-            }
-        }
-    }));
 
     {
         let params = BloxrouteTransactionRequestInclude {
@@ -73,16 +49,23 @@ async fn main() {
             filters: None,
             blockchain_network: None,
         };
-        let _ = client
+        if let Ok(mut stream) = client
             .subscribe_to_new_txs("newTxsId".to_string(), params)
-            .await;
+            .await
+        {
+            thread_handles.push(tokio::spawn(async move {
+                while let Some(tx) = stream.next().await {
+                    println!("New tx {}", tx.txHash);
+                }
+            }));
+        }
     }
 
     {
         thread::sleep(Duration::from_millis(5000));
         let params = BloxrouteBlockRequestInclude {
             include: Some(
-                vec![
+                [
                     "hash",
                     "header",
                     "transactions",
@@ -95,9 +78,16 @@ async fn main() {
             ),
             blockchain_network: None,
         };
-        let _ = client
+        if let Ok(mut stream) = client
             .subscribe_to_new_blocks("newBlocksId".to_string(), params)
-            .await;
+            .await
+        {
+            thread_handles.push(tokio::spawn(async move {
+                while let Some(block) = stream.next().await {
+                    println!("New block {}", block.hash);
+                }
+            }));
+        }
     }
 
     let _join_rs = join_all(thread_handles).await;
@@ -1,16 +1,26 @@
 use bloxroute_sdk::{
-    models::{
-        raydium::{
-            BloxrouteRaydiumStreamSwapsPayload, CreateRouteSwapPayload,
-            CreateSwapTransactionPayload, RaydiumQuoteStepProject, RaydiumRouteStep,
-        },
-        BloxrouteResponseEnum,
+    models::raydium::{
+        BloxrouteRaydiumStreamSwapsPayload, CreateRouteSwapPayload, CreateSwapTransactionPayload,
+        RaydiumQuoteStepProject, RaydiumRouteStep,
     },
     providers::{http::BloxrouteHttpClient, ws::BloxrouteWsClient},
 };
-use futures_util::future::join_all;
+use futures_util::{future::join_all, Stream, StreamExt};
+use std::fmt::Debug;
 use tokio::task::JoinHandle;
 
+fn print_stream<S>(mut stream: S) -> JoinHandle<()>
+where
+    S: Stream + Unpin + Send + 'static,
+    S::Item: Debug,
+{
+    tokio::spawn(async move {
+        while let Some(data) = stream.next().await {
+            println!("{:#?}", data);
+        }
+    })
+}
+
 #[tokio::main]
 async fn main() {
     let endpoint = std::env::var("endpoint").unwrap();
@@ -20,85 +30,86 @@ async fn main() {
         let timeout = 5000;
         let mut client =
             BloxrouteWsClient::connect(endpoint.to_string(), auth_header.to_string(), timeout)
-                .await;
-
-        let client_receiver_clone = client.clone();
-        thread_handles.push(tokio::spawn(async move {
-            let receiver_clone = client_receiver_clone.rx.clone();
-            let mut receiver = receiver_clone.lock().await;
-            while let Some(data) = receiver.recv().await {
-                match &data {
-                    BloxrouteResponseEnum::Transaction(_) => println!("New tx"),
-                    BloxrouteResponseEnum::Subscription(sub) => println!("{:#?}", sub),
-                    BloxrouteResponseEnum::Block(_) => println!("New block"),
-                    BloxrouteResponseEnum::OpenbookGetMarkets(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::OpenbookGetTickers(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::OpenbookGetOrderbookResponse(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::OpenbookGetDepth(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::GetBundleTipStream(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::GetStreamPriorityFee(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::RaydiumStreamReservesResponse(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::RaydiumStreamSwapsResponse(x) => println!("{:#?}", x),
-                    BloxrouteResponseEnum::RaydiumNewRaydiumPoolsResponse(x) => {
-                        println!("{:#?}", x)
-                    }
-                }
-            }
-        }));
+                .await
+                .unwrap();
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_openbook_get_markets("getMarketsId".to_string())
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_openbook_get_orderbooks(
-                    "getMarketsId".to_string(),
+                    "getOrderbooksId".to_string(),
                     "DAmWVivkjjnwN7J6hUdEpfVXcTGY4PWawKeUL7zSf76z".to_string(),
                     Some(10),
                 )
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_openbook_get_depth(
-                    "getMarketsId".to_string(),
+                    "getDepthId".to_string(),
                     "DAmWVivkjjnwN7J6hUdEpfVXcTGY4PWawKeUL7zSf76z".to_string(),
                     Some(10),
                 )
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
+        }
+        {
+            let stream = client
+                .subscribe_to_openbook_tickers_stream(
+                    "tickersStreamId".to_string(),
+                    vec!["SOL/USDC".to_string()],
+                )
+                .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
-        // {
-        //     let _ = client
-        //         .subscribe_to_openbook_get_tickers("getTickersId".to_string(), vec![])
-        //         .await;
-        // }
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_stream_priority_fee(
                     "streamPriorityFeeId".to_string(),
                     "P_RAYDIUM".to_string(),
                     Some(55.0),
                 )
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_stream_bundle_tip("streamBundleTipId".to_string())
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_stream_pool_reserves(
                     "poolReservesStreamId".to_string(),
                     vec!["58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string()],
                 )
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
 
         {
@@ -106,15 +117,21 @@ async fn main() {
                 pools: vec!["58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string()],
                 includeFailed: None,
             };
-            let _ = client
+            let stream = client
                 .subscribe_to_stream_swaps("streamSwapsId".to_string(), payload)
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
 
         {
-            let _ = client
+            let stream = client
                 .subscribe_to_new_raydium_pools("newRaydiumPoolsId".to_string(), Some(true))
                 .await;
+            if let Ok(stream) = stream {
+                thread_handles.push(print_stream(stream));
+            }
         }
     }
 
@@ -134,7 +151,7 @@ async fn main() {
         }
 
         {
-            let pairs = vec![
+            let pairs = [
                 "A43RUCwVhHCfsCYHUDsqdsJEZrXiytaRdpPN2XqVn74n",
                 "EKsyVyGcTL6Wc9REgCeq1d9rJg94a843W3JZNDRi5hRJ",
             ]
@@ -0,0 +1,190 @@
+use std::{error::Error as StdError, time::Duration};
+
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio_tungstenite::tungstenite;
+
+/// Error returned by every call of the SDK.
+#[derive(Debug, thiserror::Error)]
+pub enum BloxrouteError {
+    /// The HTTP request could not be sent or its response could not be read.
+    #[error("transport error: {0}")]
+    Transport(#[source] reqwest::Error),
+    /// The websocket could not be opened or failed while in use. Boxed as it dwarfs the other
+    /// variants.
+    #[error("websocket error: {0}")]
+    WebSocket(#[source] Box<tungstenite::Error>),
+    #[error("TLS error: {0}")]
+    Tls(String),
+    /// The server answered with a non-success status not covered by a more specific variant.
+    #[error("HTTP error {status}: {body}")]
+    Http { status: u16, body: String },
+    /// The gateway answered a JSON-RPC request with an error object.
+    #[error("JSON-RPC error {code}: {message}")]
+    JsonRpc {
+        code: i64,
+        message: String,
+        data: Option<Value>,
+    },
+    /// The response did not match the expected model, `body` holds what was received.
+    #[error("failed to deserialize response: {source}")]
+    Deserialization {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+    /// The auth header is missing, malformed or was rejected.
+    #[error("authentication failed: {0}")]
+    Auth(String),
+    #[error("rate limited: {body}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        body: String,
+    },
+    /// A raw transaction could not be decoded or its signature could not be recovered.
+    #[error("invalid raw transaction: {0}")]
+    RawTransaction(String),
+    /// A stream filter was rejected before subscribing.
+    #[error("invalid filter: {0}")]
+    InvalidFilter(String),
+    /// An update could not be applied to a local order book.
+    #[error("order book error: {0}")]
+    OrderBook(String),
+    /// The connection closed before the response arrived.
+    #[error("connection closed")]
+    ConnectionClosed,
+    #[error("timed out")]
+    Timeout,
+}
+
+impl BloxrouteError {
+    /// Whether the same call may succeed if retried later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BloxrouteError::Transport(err) => err.is_timeout() || err.is_connect(),
+            BloxrouteError::WebSocket(err) => matches!(
+                **err,
+                tungstenite::Error::Io(_)
+                    | tungstenite::Error::ConnectionClosed
+                    | tungstenite::Error::AlreadyClosed
+            ),
+            BloxrouteError::Http { status, .. } => *status >= 500,
+            BloxrouteError::RateLimited { .. }
+            | BloxrouteError::ConnectionClosed
+            | BloxrouteError::Timeout => true,
+            BloxrouteError::Tls(_)
+            | BloxrouteError::JsonRpc { .. }
+            | BloxrouteError::Deserialization { .. }
+            | BloxrouteError::Auth(_)
+            | BloxrouteError::RawTransaction(_)
+            | BloxrouteError::InvalidFilter(_)
+            | BloxrouteError::OrderBook(_) => false,
+        }
+    }
+
+    /// Maps a non-success HTTP response to the matching variant.
+    pub fn from_status(status: StatusCode, headers: &HeaderMap, body: String) -> BloxrouteError {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => BloxrouteError::Auth(body),
+            StatusCode::TOO_MANY_REQUESTS => BloxrouteError::RateLimited {
+                retry_after: headers
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse().ok())
+                    .map(Duration::from_secs),
+                body,
+            },
+            _ => BloxrouteError::Http {
+                status: status.as_u16(),
+                body,
+            },
+        }
+    }
+
+    pub fn deserialization(source: serde_json::Error, body: &str) -> BloxrouteError {
+        BloxrouteError::Deserialization {
+            source,
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Error object of a JSON-RPC response.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteJsonRpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl From<BloxrouteJsonRpcError> for BloxrouteError {
+    fn from(err: BloxrouteJsonRpcError) -> BloxrouteError {
+        BloxrouteError::JsonRpc {
+            code: err.code,
+            message: err.message,
+            data: err.data,
+        }
+    }
+}
+
+impl From<reqwest::Error> for BloxrouteError {
+    fn from(err: reqwest::Error) -> BloxrouteError {
+        let mut source = err.source();
+        while let Some(inner) = source {
+            if let Some(tls) = inner.downcast_ref::<native_tls::Error>() {
+                return BloxrouteError::Tls(tls.to_string());
+            }
+            source = inner.source();
+        }
+        BloxrouteError::Transport(err)
+    }
+}
+
+impl From<tungstenite::Error> for BloxrouteError {
+    fn from(err: tungstenite::Error) -> BloxrouteError {
+        match err {
+            tungstenite::Error::Tls(err) => BloxrouteError::Tls(err.to_string()),
+            tungstenite::Error::Http(response) => {
+                let body = response
+                    .body()
+                    .as_ref()
+                    .map(|body| String::from_utf8_lossy(body).to_string())
+                    .unwrap_or_default();
+                BloxrouteError::from_status(response.status(), response.headers(), body)
+            }
+            err => BloxrouteError::WebSocket(Box::new(err)),
+        }
+    }
+}
+
+impl From<native_tls::Error> for BloxrouteError {
+    fn from(err: native_tls::Error) -> BloxrouteError {
+        BloxrouteError::Tls(err.to_string())
+    }
+}
+
+/// Raw socket errors only come from the WS client (e.g. while tunneling through a proxy).
+impl From<std::io::Error> for BloxrouteError {
+    fn from(err: std::io::Error) -> BloxrouteError {
+        BloxrouteError::WebSocket(Box::new(tungstenite::Error::Io(err)))
+    }
+}
+
+impl From<tungstenite::http::header::InvalidHeaderValue> for BloxrouteError {
+    fn from(err: tungstenite::http::header::InvalidHeaderValue) -> BloxrouteError {
+        BloxrouteError::Auth(format!("invalid auth header: {err}"))
+    }
+}
+
+impl From<alloy_rlp::Error> for BloxrouteError {
+    fn from(err: alloy_rlp::Error) -> Self {
+        BloxrouteError::RawTransaction(err.to_string())
+    }
+}
+
+impl From<alloy_primitives::hex::FromHexError> for BloxrouteError {
+    fn from(err: alloy_primitives::hex::FromHexError) -> Self {
+        BloxrouteError::RawTransaction(err.to_string())
+    }
+}
@@ -1,2 +1,3 @@
-pub mod providers;
+pub mod error;
 pub mod models;
+pub mod providers;
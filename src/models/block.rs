@@ -1,55 +1,46 @@
+use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256};
 use serde::Deserialize;
+
+use crate::models::{quantity, transaction::BloxrouteTransactionContents};
+
 #[allow(non_snake_case)]
 #[derive(Deserialize, Debug, Clone)]
 pub struct BloxrouteBlockHeader {
-    pub parentHash: String,
-    pub sha3Uncles: String,
-    pub miner: String,
-    pub stateRoot: String,
-    pub transactionsRoot: String,
-    pub receiptsRoot: String,
-    pub logsBloom: String,
-    pub difficulty: String,
-    pub number: String,
-    pub gasLimit: String,
-    pub gasUsed: String,
-    pub timestamp: String,
-    pub extraData: String,
-    pub mixHash: String,
-    pub nonce: String,
+    pub parentHash: B256,
+    pub sha3Uncles: B256,
+    pub miner: Address,
+    pub stateRoot: B256,
+    pub transactionsRoot: B256,
+    pub receiptsRoot: B256,
+    pub logsBloom: Bloom,
+    pub difficulty: U256,
+    #[serde(with = "quantity")]
+    pub number: u64,
+    #[serde(with = "quantity")]
+    pub gasLimit: u64,
+    #[serde(with = "quantity")]
+    pub gasUsed: u64,
+    #[serde(with = "quantity")]
+    pub timestamp: u64,
+    pub extraData: Bytes,
+    pub mixHash: B256,
+    pub nonce: B64,
+    #[serde(with = "quantity")]
     pub baseFeePerGas: u64,
-    pub withdrawalsRoot: String,
-    pub blobGasUsed: String,
-    pub excessBlobGas: String,
-    pub parentBeaconBlockRoot: String,
+    pub withdrawalsRoot: B256,
+    #[serde(with = "quantity")]
+    pub blobGasUsed: u64,
+    #[serde(with = "quantity")]
+    pub excessBlobGas: u64,
+    pub parentBeaconBlockRoot: B256,
 }
 
-#[allow(non_snake_case)]
-#[derive(Deserialize, Debug, Clone)]
-pub struct BloxrouteBlockTransaction {
-    // accessList: [],
-    // blobVersionedHashes: [],
-    pub chainId: Option<String>,
-    pub from: String,
-    pub gas: String,
-    pub gasPrice: Option<String>,
-    pub hash: String,
-    pub input: String,
-    pub maxFeePerGas: Option<String>,
-    pub maxPriorityFeePerGas: Option<String>,
-    pub nonce: String,
-    pub r: String,
-    pub s: String,
-    pub to: String,
-    pub r#type: String,
-    pub v: String,
-    pub value: String,
-    pub yParity: Option<String>,
-}
+/// Transactions of a block are sent with all their fields.
+pub type BloxrouteBlockTransaction = BloxrouteTransactionContents;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct BloxrouteBlock {
-    pub hash: String,
+    pub hash: B256,
     pub header: BloxrouteBlockHeader,
     pub transactions: Vec<BloxrouteBlockTransaction>,
 }
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Params of `blxr_submit_bundle`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteBundle {
+    /// Raw signed transactions, hex encoded without the `0x` prefix, in execution order.
+    pub transaction: Vec<String>,
+    /// Hex encoded number of the block the bundle targets.
+    pub block_number: String,
+    /// Unix timestamps bounding when the bundle is valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<u64>,
+    /// Hashes of the transactions allowed to revert.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverting_hashes: Option<Vec<String>>,
+    /// Resubmitting with the same uuid replaces the bundle, an empty `transaction` cancels it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+    /// Builders to send the bundle to, e.g. `["all"]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builders: Option<Vec<String>>,
+    /// Builder name -> signature, e.g. `{"flashbots": "<signature>"}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mev_builders: Option<HashMap<String, String>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteSubmitBundleResponse {
+    /// Empty when a bundle is cancelled.
+    #[serde(default)]
+    pub bundleHash: String,
+}
+
+/// Params of `blxr_simulate_bundle`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteSimulateBundleRequest {
+    pub transaction: Vec<String>,
+    pub block_number: String,
+    /// Block whose state the bundle runs on, e.g. `latest` or a hex number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_block_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+}
+
+impl From<&BloxrouteBundle> for BloxrouteSimulateBundleRequest {
+    /// Simulates the bundle on top of the latest state.
+    fn from(bundle: &BloxrouteBundle) -> Self {
+        Self {
+            transaction: bundle.transaction.clone(),
+            block_number: bundle.block_number.clone(),
+            state_block_number: Some("latest".to_string()),
+            timestamp: None,
+            blockchain_network: bundle.blockchain_network.clone(),
+        }
+    }
+}
+
+/// Outcome of a single transaction of a simulated bundle. Amounts are decimal strings in wei.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteBundleSimulationTx {
+    pub txHash: String,
+    pub fromAddress: Option<String>,
+    pub toAddress: Option<String>,
+    pub gasUsed: u64,
+    pub gasPrice: Option<String>,
+    pub gasFees: Option<String>,
+    pub coinbaseDiff: Option<String>,
+    pub ethSentToCoinbase: Option<String>,
+    pub value: Option<String>,
+    pub error: Option<String>,
+    /// Revert reason, if the transaction reverted.
+    pub revert: Option<String>,
+}
+
+impl BloxrouteBundleSimulationTx {
+    pub fn reverted(&self) -> bool {
+        self.error.is_some() || self.revert.is_some()
+    }
+}
+
+/// Result of `blxr_simulate_bundle`. Amounts are decimal strings in wei.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteBundleSimulation {
+    pub bundleHash: String,
+    pub bundleGasPrice: Option<String>,
+    pub coinbaseDiff: Option<String>,
+    pub ethSentToCoinbase: Option<String>,
+    pub gasFees: Option<String>,
+    pub results: Vec<BloxrouteBundleSimulationTx>,
+    pub stateBlockNumber: Option<u64>,
+    pub totalGasUsed: u64,
+}
+
+impl BloxrouteBundleSimulation {
+    /// Transactions of the bundle that reverted or failed.
+    pub fn reverted(&self) -> impl Iterator<Item = &BloxrouteBundleSimulationTx> {
+        self.results.iter().filter(|tx| tx.reverted())
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use alloy_primitives::{Bytes, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::models::quantity;
+
+/// Replaced by the gateway with the height of the new block in any call parameter.
+pub const BLOCK_HEIGHT_PLACEHOLDER: &str = "{BlockHeight}";
+
+/// Name of the event sent once every call of a block has been answered.
+pub const TASK_COMPLETED_EVENT: &str = "TaskCompletedEvent";
+
+/// A call run at every new block. `name` correlates the call with its responses.
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "method")]
+pub enum BloxrouteEthOnBlockCall {
+    #[serde(rename = "eth_call")]
+    Call {
+        name: String,
+        to: String,
+        data: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        from: Option<String>,
+        /// Block to run against, e.g. `latest` or `{BlockHeight}`.
+        tag: String,
+    },
+    #[serde(rename = "eth_getBalance")]
+    GetBalance {
+        name: String,
+        address: String,
+        tag: String,
+    },
+    #[serde(rename = "eth_blockNumber")]
+    BlockNumber { name: String },
+}
+
+impl BloxrouteEthOnBlockCall {
+    pub fn name(&self) -> &str {
+        match self {
+            BloxrouteEthOnBlockCall::Call { name, .. }
+            | BloxrouteEthOnBlockCall::GetBalance { name, .. }
+            | BloxrouteEthOnBlockCall::BlockNumber { name } => name,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BloxrouteEthOnBlockRequest {
+    pub include: Option<Vec<String>>,
+    #[serde(rename = "call-params")]
+    pub call_params: Vec<BloxrouteEthOnBlockCall>,
+    pub blockchain_network: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteEthOnBlockResponse {
+    /// Name of the call this answers, or `TaskCompletedEvent`.
+    pub name: String,
+    /// Raw result of the call, use the accessor matching the call's method to decode it.
+    pub response: String,
+    #[serde(with = "quantity")]
+    pub blockHeight: u64,
+    pub tag: Option<String>,
+}
+
+impl BloxrouteEthOnBlockResponse {
+    /// Whether this marks the end of the responses for `blockHeight`.
+    pub fn is_task_completed(&self) -> bool {
+        self.name == TASK_COMPLETED_EVENT
+    }
+
+    /// The response of an `eth_getBalance` call.
+    pub fn balance(&self) -> Option<U256> {
+        self.response.parse().ok()
+    }
+
+    /// The response of an `eth_blockNumber` call.
+    pub fn block_number(&self) -> Option<u64> {
+        self.balance()?.try_into().ok()
+    }
+
+    /// The data returned by an `eth_call` call.
+    pub fn call_output(&self) -> Option<Bytes> {
+        self.response.parse().ok()
+    }
+}
+
+/// Responses of every call run at a block, keyed by call name.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default)]
+pub struct BloxrouteEthOnBlockResults {
+    pub blockHeight: u64,
+    pub responses: HashMap<String, BloxrouteEthOnBlockResponse>,
+}
+
+impl BloxrouteEthOnBlockResults {
+    pub fn get(&self, name: &str) -> Option<&BloxrouteEthOnBlockResponse> {
+        self.responses.get(name)
+    }
+}
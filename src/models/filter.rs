@@ -0,0 +1,350 @@
+use std::fmt::{self, Display, Formatter};
+
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::BloxrouteError;
+
+/// Transaction fields the `newTxs` and `pendingTxs` streams can be filtered on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BloxrouteFilterField {
+    To,
+    From,
+    /// Value in wei.
+    Value,
+    GasPrice,
+    MaxFeePerGas,
+    MaxPriorityFeePerGas,
+    /// First 4 bytes of the input, e.g. `a9059cbb`.
+    MethodId,
+    Type,
+    ChainId,
+}
+
+impl BloxrouteFilterField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BloxrouteFilterField::To => "to",
+            BloxrouteFilterField::From => "from",
+            BloxrouteFilterField::Value => "value",
+            BloxrouteFilterField::GasPrice => "gas_price",
+            BloxrouteFilterField::MaxFeePerGas => "max_fee_per_gas",
+            BloxrouteFilterField::MaxPriorityFeePerGas => "max_priority_fee_per_gas",
+            BloxrouteFilterField::MethodId => "method_id",
+            BloxrouteFilterField::Type => "type",
+            BloxrouteFilterField::ChainId => "chain_id",
+        }
+    }
+
+    /// Whether the field is compared as a number rather than as a hex string.
+    pub fn is_numeric(&self) -> bool {
+        !matches!(
+            self,
+            BloxrouteFilterField::To | BloxrouteFilterField::From | BloxrouteFilterField::MethodId
+        )
+    }
+
+    pub fn eq(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Eq, value)
+    }
+
+    pub fn ne(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Ne, value)
+    }
+
+    pub fn gt(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Gt, value)
+    }
+
+    pub fn ge(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Ge, value)
+    }
+
+    pub fn lt(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Lt, value)
+    }
+
+    pub fn le(self, value: impl Into<BloxrouteFilterValue>) -> BloxrouteFilter {
+        self.compare(BloxrouteFilterOp::Le, value)
+    }
+
+    pub fn is_in<V: Into<BloxrouteFilterValue>>(
+        self,
+        values: impl IntoIterator<Item = V>,
+    ) -> BloxrouteFilter {
+        BloxrouteFilter::In {
+            field: self,
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn compare(
+        self,
+        op: BloxrouteFilterOp,
+        value: impl Into<BloxrouteFilterValue>,
+    ) -> BloxrouteFilter {
+        BloxrouteFilter::Compare {
+            field: self,
+            op,
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloxrouteFilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl BloxrouteFilterOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BloxrouteFilterOp::Eq => "==",
+            BloxrouteFilterOp::Ne => "!=",
+            BloxrouteFilterOp::Gt => ">",
+            BloxrouteFilterOp::Ge => ">=",
+            BloxrouteFilterOp::Lt => "<",
+            BloxrouteFilterOp::Le => "<=",
+        }
+    }
+}
+
+/// Right-hand side of a comparison: numbers are rendered as is, text in single quotes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BloxrouteFilterValue {
+    Number(U256),
+    Text(String),
+}
+
+impl From<u64> for BloxrouteFilterValue {
+    fn from(value: u64) -> Self {
+        BloxrouteFilterValue::Number(U256::from(value))
+    }
+}
+
+impl From<u128> for BloxrouteFilterValue {
+    fn from(value: u128) -> Self {
+        BloxrouteFilterValue::Number(U256::from(value))
+    }
+}
+
+impl From<U256> for BloxrouteFilterValue {
+    fn from(value: U256) -> Self {
+        BloxrouteFilterValue::Number(value)
+    }
+}
+
+impl From<Address> for BloxrouteFilterValue {
+    fn from(value: Address) -> Self {
+        BloxrouteFilterValue::Text(value.to_string().to_lowercase())
+    }
+}
+
+impl From<&str> for BloxrouteFilterValue {
+    fn from(value: &str) -> Self {
+        BloxrouteFilterValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for BloxrouteFilterValue {
+    fn from(value: String) -> Self {
+        BloxrouteFilterValue::Text(value)
+    }
+}
+
+impl Display for BloxrouteFilterValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BloxrouteFilterValue::Number(value) => write!(f, "{}", value),
+            BloxrouteFilterValue::Text(value) => {
+                write!(f, "'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+            }
+        }
+    }
+}
+
+/// Filter expression of the `newTxs` and `pendingTxs` streams, rendered in bloXroute's syntax.
+///
+/// ```
+/// use bloxroute_sdk::models::filter::BloxrouteFilterField::{MethodId, To, Value};
+///
+/// let filter = To
+///     .eq("0x7a250d5630b4cf539739df2c5dacb4c659f2488d")
+///     .and(Value.gt(1_000_000_000_000_000_000u64).or(MethodId.is_in(["a9059cbb"])));
+/// assert_eq!(
+///     filter.to_string(),
+///     "({to} == '0x7a250d5630b4cf539739df2c5dacb4c659f2488d') AND \
+///      (({value} > 1000000000000000000) OR ({method_id} IN ['a9059cbb']))"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BloxrouteFilter {
+    Compare {
+        field: BloxrouteFilterField,
+        op: BloxrouteFilterOp,
+        value: BloxrouteFilterValue,
+    },
+    In {
+        field: BloxrouteFilterField,
+        values: Vec<BloxrouteFilterValue>,
+    },
+    And(Vec<BloxrouteFilter>),
+    Or(Vec<BloxrouteFilter>),
+    /// Expression sent as is, only checked by the gateway.
+    Raw(String),
+}
+
+impl BloxrouteFilter {
+    pub fn raw(expression: impl Into<String>) -> Self {
+        BloxrouteFilter::Raw(expression.into())
+    }
+
+    pub fn and(self, other: BloxrouteFilter) -> Self {
+        match self {
+            BloxrouteFilter::And(mut filters) => {
+                filters.push(other);
+                BloxrouteFilter::And(filters)
+            }
+            filter => BloxrouteFilter::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: BloxrouteFilter) -> Self {
+        match self {
+            BloxrouteFilter::Or(mut filters) => {
+                filters.push(other);
+                BloxrouteFilter::Or(filters)
+            }
+            filter => BloxrouteFilter::Or(vec![filter, other]),
+        }
+    }
+
+    /// Checks the expression against what the gateway accepts, so mistakes surface before
+    /// subscribing.
+    pub fn validate(&self) -> Result<(), BloxrouteError> {
+        match self {
+            BloxrouteFilter::Compare { field, op, value } => {
+                if !field.is_numeric()
+                    && !matches!(op, BloxrouteFilterOp::Eq | BloxrouteFilterOp::Ne)
+                {
+                    return Err(invalid(format!(
+                        "{{{}}} cannot be compared with {}",
+                        field.name(),
+                        op.symbol()
+                    )));
+                }
+                validate_value(*field, value)
+            }
+            BloxrouteFilter::In { field, values } => {
+                if values.is_empty() {
+                    return Err(invalid(format!("empty IN list for {{{}}}", field.name())));
+                }
+                values
+                    .iter()
+                    .try_for_each(|value| validate_value(*field, value))
+            }
+            BloxrouteFilter::And(filters) | BloxrouteFilter::Or(filters) => {
+                if filters.is_empty() {
+                    return Err(invalid("empty AND/OR expression".to_string()));
+                }
+                filters.iter().try_for_each(BloxrouteFilter::validate)
+            }
+            BloxrouteFilter::Raw(expression) if expression.trim().is_empty() => {
+                Err(invalid("empty expression".to_string()))
+            }
+            BloxrouteFilter::Raw(_) => Ok(()),
+        }
+    }
+
+    /// Renders an operand of `AND`/`OR`, parenthesizing nested expressions.
+    fn fmt_operand(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BloxrouteFilter::And(_) | BloxrouteFilter::Or(_) | BloxrouteFilter::Raw(_) => {
+                write!(f, "({})", self)
+            }
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+fn validate_value(
+    field: BloxrouteFilterField,
+    value: &BloxrouteFilterValue,
+) -> Result<(), BloxrouteError> {
+    let hex_len = match (field, value) {
+        (field, BloxrouteFilterValue::Number(_)) if field.is_numeric() => return Ok(()),
+        (
+            BloxrouteFilterField::To | BloxrouteFilterField::From,
+            BloxrouteFilterValue::Text(text),
+        ) => text.strip_prefix("0x").map(|hex| (hex, 40)),
+        (BloxrouteFilterField::MethodId, BloxrouteFilterValue::Text(text)) => {
+            Some((text.strip_prefix("0x").unwrap_or(text), 8))
+        }
+        _ => None,
+    };
+    match hex_len {
+        Some((hex, len)) if hex.len() == len && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            Ok(())
+        }
+        _ => Err(invalid(format!(
+            "invalid value {} for {{{}}}",
+            value,
+            field.name()
+        ))),
+    }
+}
+
+fn invalid(message: String) -> BloxrouteError {
+    BloxrouteError::InvalidFilter(message)
+}
+
+impl Display for BloxrouteFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BloxrouteFilter::Compare { field, op, value } => {
+                write!(f, "({{{}}} {} {})", field.name(), op.symbol(), value)
+            }
+            BloxrouteFilter::In { field, values } => {
+                write!(f, "({{{}}} IN [", field.name())?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "])")
+            }
+            BloxrouteFilter::And(filters) | BloxrouteFilter::Or(filters) => {
+                let separator = match self {
+                    BloxrouteFilter::And(_) => " AND ",
+                    _ => " OR ",
+                };
+                for (i, filter) in filters.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", separator)?;
+                    }
+                    filter.fmt_operand(f)?;
+                }
+                Ok(())
+            }
+            BloxrouteFilter::Raw(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+impl Serialize for BloxrouteFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BloxrouteFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(BloxrouteFilter::Raw)
+    }
+}
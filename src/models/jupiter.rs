@@ -0,0 +1,31 @@
+//! Jupiter specific models, quotes and swaps use the types shared in `models::solana`.
+
+use serde::{Deserialize, Serialize};
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JupiterAccountMeta {
+    pub programKey: String,
+    pub isSigner: bool,
+    pub isWritable: bool,
+}
+
+/// Instruction of a swap, `data` is base64 encoded.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JupiterInstruction {
+    pub programID: String,
+    pub accounts: Vec<JupiterAccountMeta>,
+    pub data: String,
+}
+
+/// Instructions of a swap, to be added to a transaction built by the caller along with the
+/// address lookup tables they reference.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JupiterSwapInstructionsResponse {
+    pub priceImpactPercent: Option<f64>,
+    pub instructions: Vec<JupiterInstruction>,
+    #[serde(default)]
+    pub addressLookupTableAddresses: Vec<String>,
+}
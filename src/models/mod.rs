@@ -1,21 +1,38 @@
 pub mod block;
+pub mod bundle;
+pub mod eth_on_block;
+pub mod filter;
+pub mod jupiter;
 pub mod openbook;
+pub mod orderbook;
+pub mod quantity;
+pub mod raw_tx;
 pub mod raydium;
+pub mod receipt;
 pub mod solana;
 pub mod subscription;
 pub mod transaction;
 use std::collections::HashMap;
 
+use crate::error::BloxrouteJsonRpcError;
 use block::BloxrouteBlock;
+use eth_on_block::BloxrouteEthOnBlockResponse;
 use openbook::{
     BloxrouteOpenbookGetDepthResponse, BloxrouteOpenbookGetMarketsResponse,
-    BloxrouteOpenbookGetOrderbookResponse, BloxrouteOpenbookGetTickersResponse,
+    BloxrouteOpenbookGetOpenOrdersResponse, BloxrouteOpenbookGetOrderbookResponse,
+    BloxrouteOpenbookGetTickersResponse, BloxrouteOpenbookGetUnsettledResponse,
+    BloxrouteOpenbookOrderbookStreamResponse, BloxrouteOpenbookTickerStreamResponse,
 };
-use raydium::{BloxrouteRaydiumNewRaydiumPoolsResponse, BloxrouteRaydiumStreamReservesResponse, BloxrouteRaydiumStreamSwapsResponse};
-use serde::{Deserialize, Serialize};
+use raydium::{
+    BloxrouteRaydiumNewRaydiumPoolsResponse, BloxrouteRaydiumStreamReservesResponse,
+    BloxrouteRaydiumStreamSwapsResponse,
+};
+use receipt::BloxrouteTxReceipt;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
 use solana::{BloxrouteGetBundleTipStreamResponse, BloxrouteGetStreamPriorityFee};
 use subscription::BloxrouteSubscription;
-use transaction::BloxrouteTransaction;
+use transaction::{BloxrouteTransaction, BloxrouteTransactionStatus};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct BloxrouteGenericInner<T> {
@@ -36,11 +53,90 @@ pub struct BloxrouteGenericSolana<T> {
     pub result: T,
 }
 
+/// Outer shape shared by every frame the gateway sends, with the payload left unparsed.
+#[derive(Deserialize, Debug)]
+pub struct BloxrouteEnvelope<'a> {
+    #[serde(default, deserialize_with = "deserialize_id")]
+    pub id: Option<String>,
+    pub jsonrpc: Option<String>,
+    pub method: Option<String>,
+    #[serde(borrow)]
+    pub params: Option<BloxrouteEnvelopeParams<'a>>,
+    #[serde(borrow)]
+    pub result: Option<&'a RawValue>,
+    #[serde(borrow)]
+    pub error: Option<&'a RawValue>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BloxrouteEnvelopeParams<'a> {
+    pub subscription: String,
+    #[serde(borrow)]
+    pub result: &'a RawValue,
+}
+
+/// Request ids are echoed back as sent, but accept numeric ids from other clients too.
+fn deserialize_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Id {
+        String(String),
+        Number(u64),
+    }
+    Ok(Option::<Id>::deserialize(deserializer)?.map(|id| match id {
+        Id::String(id) => id,
+        Id::Number(id) => id.to_string(),
+    }))
+}
+
+/// Builds a typed message from an envelope by parsing only its payload.
+pub trait FromEnvelope: Sized {
+    fn from_envelope(envelope: &BloxrouteEnvelope) -> Result<Self, serde_json::Error>;
+}
+
+impl<T: for<'de> Deserialize<'de>> FromEnvelope for BloxrouteGeneric<T> {
+    fn from_envelope(envelope: &BloxrouteEnvelope) -> Result<Self, serde_json::Error> {
+        let params = envelope
+            .params
+            .as_ref()
+            .ok_or_else(|| serde::de::Error::missing_field("params"))?;
+        Ok(Self {
+            jsonrpc: envelope.jsonrpc.clone().unwrap_or_default(),
+            method: envelope.method.clone().unwrap_or_default(),
+            params: BloxrouteGenericInner {
+                result: serde_json::from_str(params.result.get())?,
+                subscription: params.subscription.clone(),
+            },
+        })
+    }
+}
+
+impl<T: for<'de> Deserialize<'de>> FromEnvelope for BloxrouteGenericSolana<T> {
+    fn from_envelope(envelope: &BloxrouteEnvelope) -> Result<Self, serde_json::Error> {
+        let result = envelope
+            .result
+            .ok_or_else(|| serde::de::Error::missing_field("result"))?;
+        Ok(Self {
+            id: envelope.id.clone().unwrap_or_default(),
+            result: serde_json::from_str(result.get())?,
+        })
+    }
+}
+
+impl<T: FromEnvelope> FromEnvelope for Box<T> {
+    fn from_envelope(envelope: &BloxrouteEnvelope) -> Result<Self, serde_json::Error> {
+        T::from_envelope(envelope).map(Box::new)
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub enum BloxrouteResponseEnum {
     Subscription(BloxrouteSubscription),
-    Transaction(BloxrouteGeneric<BloxrouteTransaction>),
-    Block(BloxrouteGeneric<BloxrouteBlock>),
+    Transaction(Box<BloxrouteGeneric<BloxrouteTransaction>>),
+    Block(Box<BloxrouteGeneric<BloxrouteBlock>>),
+    TransactionStatus(BloxrouteGeneric<BloxrouteTransactionStatus>),
+    TxReceipt(Box<BloxrouteGeneric<BloxrouteTxReceipt>>),
+    EthOnBlock(BloxrouteGeneric<BloxrouteEthOnBlockResponse>),
     GetStreamPriorityFee(BloxrouteGeneric<BloxrouteGetStreamPriorityFee>),
     GetBundleTipStream(BloxrouteGeneric<BloxrouteGetBundleTipStreamResponse>),
     RaydiumStreamReservesResponse(BloxrouteGeneric<BloxrouteRaydiumStreamReservesResponse>),
@@ -50,6 +146,10 @@ pub enum BloxrouteResponseEnum {
     OpenbookGetOrderbookResponse(BloxrouteGenericSolana<BloxrouteOpenbookGetOrderbookResponse>),
     OpenbookGetDepth(BloxrouteGenericSolana<BloxrouteOpenbookGetDepthResponse>),
     OpenbookGetTickers(BloxrouteGenericSolana<BloxrouteOpenbookGetTickersResponse>),
+    OpenbookGetOpenOrders(BloxrouteGenericSolana<BloxrouteOpenbookGetOpenOrdersResponse>),
+    OpenbookGetUnsettled(BloxrouteGenericSolana<BloxrouteOpenbookGetUnsettledResponse>),
+    OpenbookOrderbooksStream(BloxrouteGeneric<BloxrouteOpenbookOrderbookStreamResponse>),
+    OpenbookTickersStream(BloxrouteGeneric<BloxrouteOpenbookTickerStreamResponse>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -62,6 +162,14 @@ pub enum BloxrouteRequestParams<T> {
     Hashmap(HashMap<String, T>),
 }
 
+/// Response to a JSON-RPC request, carrying either a result or an error.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteJsonRpcResponse<T> {
+    pub id: Option<serde_json::Value>,
+    pub result: Option<T>,
+    pub error: Option<BloxrouteJsonRpcError>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BloxrouteRequestPayload<T> {
     pub id: String,
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{quantity, solana::TransactionMessage};
 
 // Get markets
 
@@ -76,3 +78,168 @@ pub struct BloxrouteOpenbookGetTickerResponse {
 pub struct BloxrouteOpenbookGetTickersResponse {
     pub tickers: Vec<BloxrouteOpenbookGetTickerResponse>,
 }
+
+// Orderbooks and tickers streams
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookOrderbooksStreamPayload {
+    pub markets: Vec<String>,
+    /// Number of levels per side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+/// Book of a market, sent whenever it changes.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookOrderbookStreamResponse {
+    #[serde(with = "quantity")]
+    pub slot: u64,
+    pub orderbook: BloxrouteOpenbookGetOrderbookResponse,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookTickerStreamResponse {
+    #[serde(with = "quantity")]
+    pub slot: u64,
+    pub ticker: BloxrouteOpenbookGetTickerResponse,
+}
+
+// Get open orders
+
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookOpenOrder {
+    pub orderID: String,
+    pub market: String,
+    pub side: BloxrouteOpenbookSide,
+    /// e.g. `OT_LIMIT`.
+    pub types: Vec<String>,
+    pub price: f64,
+    pub remainingSize: f64,
+    pub createdAt: Option<String>,
+    pub clientOrderID: String,
+    pub openOrderAccount: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookGetOpenOrdersResponse {
+    pub orders: Vec<BloxrouteOpenbookOpenOrder>,
+}
+
+// Get unsettled
+
+/// Amounts freed by filled or cancelled orders, waiting in an open orders account to be settled.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookUnsettledAccount {
+    pub account: String,
+    pub baseToken: String,
+    pub quoteToken: String,
+    pub baseTokenAmount: f64,
+    pub quoteTokenAmount: f64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookGetUnsettledResponse {
+    pub market: String,
+    pub unsettled: Vec<BloxrouteOpenbookUnsettledAccount>,
+}
+
+// Orders
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BloxrouteOpenbookSide {
+    #[serde(alias = "S_BID")]
+    Bid,
+    #[serde(alias = "S_ASK")]
+    Ask,
+}
+
+/// Sent as `limit`, `ioc` and `postonly`, the values Trader API v2 accepts in `type`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BloxrouteOpenbookOrderType {
+    #[serde(rename = "limit", alias = "OT_LIMIT")]
+    Limit,
+    /// Immediate or cancel.
+    #[serde(rename = "ioc", alias = "OT_IOC")]
+    Ioc,
+    #[serde(rename = "postonly", alias = "OT_POST")]
+    PostOnly,
+}
+
+/// Payload of `place` and `replace`. A replace cancels the open order with the same
+/// `clientOrderID` before placing the new one.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookOrderPayload {
+    pub ownerAddress: String,
+    /// Token account paying for the order: the quote token wallet for bids, base for asks.
+    pub payerAddress: String,
+    pub market: String,
+    pub side: BloxrouteOpenbookSide,
+    pub r#type: BloxrouteOpenbookOrderType,
+    pub amount: f64,
+    pub price: f64,
+    /// Created, and returned in the response, if not provided.
+    pub openOrdersAddress: Option<String>,
+    pub clientOrderID: Option<u64>,
+    pub computeLimit: Option<u32>,
+    pub computePrice: Option<u64>,
+    pub tip: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookOrderResponse {
+    pub transaction: TransactionMessage,
+    pub openOrdersAddress: String,
+}
+
+/// Order to cancel, sent as either `orderID` or `clientOrderID`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum BloxrouteOpenbookOrderRef {
+    /// Id the market assigned to the order.
+    #[serde(rename = "orderID")]
+    OrderId(String),
+    /// Id given when placing the order.
+    #[serde(rename = "clientOrderID")]
+    ClientOrderId(u64),
+}
+
+/// Payload of `cancel`.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookCancelOrderPayload {
+    pub ownerAddress: String,
+    pub marketAddress: String,
+    pub openOrdersAddress: String,
+    pub side: BloxrouteOpenbookSide,
+    #[serde(flatten)]
+    pub order: BloxrouteOpenbookOrderRef,
+    pub computeLimit: Option<u32>,
+    pub computePrice: Option<u64>,
+    pub tip: Option<u64>,
+}
+
+/// Payload of `settle`, moving the filled amounts from the open orders account to the wallets.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookSettlePayload {
+    pub ownerAddress: String,
+    pub market: String,
+    pub baseTokenWallet: String,
+    pub quoteTokenWallet: String,
+    pub openOrdersAddress: String,
+    pub computeLimit: Option<u32>,
+    pub computePrice: Option<u64>,
+    pub tip: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteOpenbookTransactionsResponse {
+    pub transactions: Vec<TransactionMessage>,
+}
@@ -0,0 +1,339 @@
+//! Local copy of an Openbook market, seeded from a snapshot and kept current by stream updates.
+
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::{
+    error::BloxrouteError,
+    models::openbook::{
+        BloxrouteOpenbookGetDepthResponse, BloxrouteOpenbookGetOrderbookResponse,
+        BloxrouteOpenbookOrderbookStreamResponse, BloxrouteOpenbookSide,
+    },
+};
+
+/// What became of an update passed to [`BloxrouteOrderBook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloxrouteOrderBookUpdate {
+    Applied,
+    /// The update is older than the book and was dropped.
+    Stale,
+    /// The book was never seeded, or level changes skipped more slots than `max_slot_gap`: the
+    /// book must be resnapshotted before any further update is applied.
+    Gap,
+}
+
+/// Price levels of an Openbook market, with the size of every order at a price summed up.
+///
+/// ```
+/// use bloxroute_sdk::models::{openbook::BloxrouteOpenbookSide, orderbook::BloxrouteOrderBook};
+///
+/// let mut book = BloxrouteOrderBook::new("SOL/USDC");
+/// book.snapshot_levels([(149.8, 10.0), (149.7, 5.0)], [(150.0, 2.0), (150.2, 8.0)], Some(100));
+/// assert_eq!(book.best_bid(), Some((149.8, 10.0)));
+/// assert_eq!(book.spread(), Some(150.0 - 149.8));
+/// // Buying 4 takes 2 at 150.0 and 2 at 150.2.
+/// assert_eq!(book.vwap(BloxrouteOpenbookSide::Bid, 4.0), Some(150.1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BloxrouteOrderBook {
+    market: String,
+    bids: BTreeMap<Price, f64>,
+    asks: BTreeMap<Price, f64>,
+    slot: Option<u64>,
+    seeded: bool,
+    max_slot_gap: Option<u64>,
+}
+
+impl BloxrouteOrderBook {
+    /// Creates an empty book, updates are refused until it is snapshotted.
+    pub fn new(market: impl Into<String>) -> Self {
+        BloxrouteOrderBook {
+            market: market.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            slot: None,
+            seeded: false,
+            max_slot_gap: None,
+        }
+    }
+
+    pub fn from_orderbook(
+        snapshot: &BloxrouteOpenbookGetOrderbookResponse,
+        slot: Option<u64>,
+    ) -> Self {
+        let mut book = BloxrouteOrderBook::new(snapshot.market.clone());
+        book.snapshot_orderbook(snapshot, slot);
+        book
+    }
+
+    pub fn from_depth(snapshot: &BloxrouteOpenbookGetDepthResponse, slot: Option<u64>) -> Self {
+        let mut book = BloxrouteOrderBook::new(snapshot.market.clone());
+        book.snapshot_depth(snapshot, slot);
+        book
+    }
+
+    /// Treats level changes passed to `apply_deltas` further than `max_slot_gap` slots past the
+    /// last update as a gap. Full books are applied whatever the distance.
+    pub fn with_max_slot_gap(mut self, max_slot_gap: u64) -> Self {
+        self.max_slot_gap = Some(max_slot_gap);
+        self
+    }
+
+    pub fn market(&self) -> &str {
+        &self.market
+    }
+
+    /// Slot of the last snapshot or update applied, if known.
+    pub fn slot(&self) -> Option<u64> {
+        self.slot
+    }
+
+    pub fn needs_snapshot(&self) -> bool {
+        !self.seeded
+    }
+
+    /// Replaces the book with a `GetOrderbook` snapshot. Snapshots carry no slot, pass the one
+    /// they were taken at if known, otherwise the next update is accepted whatever its slot.
+    pub fn snapshot_orderbook(
+        &mut self,
+        snapshot: &BloxrouteOpenbookGetOrderbookResponse,
+        slot: Option<u64>,
+    ) {
+        self.snapshot_levels(
+            snapshot.bids.iter().map(|order| (order.price, order.size)),
+            snapshot.asks.iter().map(|order| (order.price, order.size)),
+            slot,
+        );
+    }
+
+    /// Replaces the book with a `GetDepth` snapshot.
+    pub fn snapshot_depth(
+        &mut self,
+        snapshot: &BloxrouteOpenbookGetDepthResponse,
+        slot: Option<u64>,
+    ) {
+        self.snapshot_levels(
+            snapshot.bids.iter().map(|level| (level.price, level.size)),
+            snapshot.asks.iter().map(|level| (level.price, level.size)),
+            slot,
+        );
+    }
+
+    /// Replaces the book with `(price, size)` levels.
+    pub fn snapshot_levels(
+        &mut self,
+        bids: impl IntoIterator<Item = (f64, f64)>,
+        asks: impl IntoIterator<Item = (f64, f64)>,
+        slot: Option<u64>,
+    ) {
+        self.bids = levels(bids);
+        self.asks = levels(asks);
+        self.slot = slot;
+        self.seeded = true;
+    }
+
+    /// Applies an update of `GetOrderbooksStream`, which carries every level of the market up to
+    /// the requested limit.
+    pub fn apply_update(
+        &mut self,
+        update: &BloxrouteOpenbookOrderbookStreamResponse,
+    ) -> Result<BloxrouteOrderBookUpdate, BloxrouteError> {
+        self.check_market(&update.orderbook.market)?;
+        Ok(self.apply_levels(
+            update
+                .orderbook
+                .bids
+                .iter()
+                .map(|order| (order.price, order.size)),
+            update
+                .orderbook
+                .asks
+                .iter()
+                .map(|order| (order.price, order.size)),
+            update.slot,
+        ))
+    }
+
+    /// Applies a `GetDepth` response received at `slot`.
+    pub fn apply_depth(
+        &mut self,
+        depth: &BloxrouteOpenbookGetDepthResponse,
+        slot: u64,
+    ) -> Result<BloxrouteOrderBookUpdate, BloxrouteError> {
+        self.check_market(&depth.market)?;
+        Ok(self.apply_levels(
+            depth.bids.iter().map(|level| (level.price, level.size)),
+            depth.asks.iter().map(|level| (level.price, level.size)),
+            slot,
+        ))
+    }
+
+    /// Replaces the book with every `(price, size)` level seen at `slot`, in slot order.
+    pub fn apply_levels(
+        &mut self,
+        bids: impl IntoIterator<Item = (f64, f64)>,
+        asks: impl IntoIterator<Item = (f64, f64)>,
+        slot: u64,
+    ) -> BloxrouteOrderBookUpdate {
+        if !self.seeded {
+            return BloxrouteOrderBookUpdate::Gap;
+        }
+        if self.slot.is_some_and(|last| slot < last) {
+            return BloxrouteOrderBookUpdate::Stale;
+        }
+        self.bids = levels(bids);
+        self.asks = levels(asks);
+        self.slot = Some(slot);
+        BloxrouteOrderBookUpdate::Applied
+    }
+
+    /// Merges `(price, size)` level changes seen at `slot` into the book, in slot order. A level
+    /// is set to the new size, or removed if it is zero.
+    pub fn apply_deltas(
+        &mut self,
+        bids: impl IntoIterator<Item = (f64, f64)>,
+        asks: impl IntoIterator<Item = (f64, f64)>,
+        slot: u64,
+    ) -> BloxrouteOrderBookUpdate {
+        if !self.seeded {
+            return BloxrouteOrderBookUpdate::Gap;
+        }
+        match self.slot {
+            Some(last) if slot < last => return BloxrouteOrderBookUpdate::Stale,
+            Some(last) if self.max_slot_gap.is_some_and(|max| slot - last > max) => {
+                self.seeded = false;
+                return BloxrouteOrderBookUpdate::Gap;
+            }
+            _ => {}
+        }
+        merge_levels(&mut self.bids, bids);
+        merge_levels(&mut self.asks, asks);
+        self.slot = Some(slot);
+        BloxrouteOrderBookUpdate::Applied
+    }
+
+    /// Highest bid as `(price, size)`.
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(price, size)| (price.0, *size))
+    }
+
+    /// Lowest ask as `(price, size)`.
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(price, size)| (price.0, *size))
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.0 + self.best_ask()?.0) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.0 - self.best_bid()?.0)
+    }
+
+    /// Size resting at exactly `price`, zero if there is no such level.
+    pub fn size_at(&self, side: BloxrouteOpenbookSide, price: f64) -> f64 {
+        self.side(side)
+            .get(&Price(price))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Levels of a side as `(price, size)`, best first.
+    pub fn levels(&self, side: BloxrouteOpenbookSide) -> Vec<(f64, f64)> {
+        let levels = self.side(side).iter().map(|(price, size)| (price.0, *size));
+        match side {
+            BloxrouteOpenbookSide::Bid => levels.rev().collect(),
+            BloxrouteOpenbookSide::Ask => levels.collect(),
+        }
+    }
+
+    /// Average price an order of `side` for `size` would fill at, walking the opposite side from
+    /// its best level. `None` if the book is not deep enough.
+    pub fn vwap(&self, side: BloxrouteOpenbookSide, size: f64) -> Option<f64> {
+        if size <= 0.0 {
+            return None;
+        }
+        let opposite = match side {
+            BloxrouteOpenbookSide::Bid => BloxrouteOpenbookSide::Ask,
+            BloxrouteOpenbookSide::Ask => BloxrouteOpenbookSide::Bid,
+        };
+        let (mut remaining, mut notional) = (size, 0.0);
+        for (price, level_size) in self.levels(opposite) {
+            let filled = remaining.min(level_size);
+            notional += filled * price;
+            remaining -= filled;
+            if remaining <= 0.0 {
+                return Some(notional / size);
+            }
+        }
+        None
+    }
+
+    fn side(&self, side: BloxrouteOpenbookSide) -> &BTreeMap<Price, f64> {
+        match side {
+            BloxrouteOpenbookSide::Bid => &self.bids,
+            BloxrouteOpenbookSide::Ask => &self.asks,
+        }
+    }
+
+    fn check_market(&self, market: &str) -> Result<(), BloxrouteError> {
+        if market != self.market {
+            return Err(BloxrouteError::OrderBook(format!(
+                "update for {} applied to the {} book",
+                market, self.market
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sums the sizes of the levels by price, dropping empty ones.
+fn levels(levels: impl IntoIterator<Item = (f64, f64)>) -> BTreeMap<Price, f64> {
+    let mut map = BTreeMap::new();
+    for (price, size) in levels {
+        if size > 0.0 {
+            *map.entry(Price(price)).or_insert(0.0) += size;
+        }
+    }
+    map
+}
+
+/// Sets the size of each changed level, removing empty ones.
+fn merge_levels(map: &mut BTreeMap<Price, f64>, changes: impl IntoIterator<Item = (f64, f64)>) {
+    for (price, size) in changes {
+        if size > 0.0 {
+            map.insert(Price(price), size);
+        } else {
+            map.remove(&Price(price));
+        }
+    }
+}
+
+/// Orders prices so they can key the levels.
+#[derive(Clone, Copy, Debug)]
+struct Price(f64);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
@@ -0,0 +1,65 @@
+//! Serde for integer quantities, sent by the gateway either as hex strings or JSON numbers.
+//!
+//! Use with `#[serde(with = "quantity")]`, or `#[serde(default, with = "quantity::opt")]` for
+//! optional fields. Quantities are serialized back as `0x` prefixed hex strings.
+
+use std::fmt::LowerHex;
+
+use alloy_primitives::U256;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// Integer types that quantities can be decoded into.
+pub trait BloxrouteQuantity: Sized + LowerHex {
+    fn from_u256(value: U256) -> Option<Self>;
+}
+
+impl BloxrouteQuantity for u64 {
+    fn from_u256(value: U256) -> Option<Self> {
+        value.try_into().ok()
+    }
+}
+
+impl BloxrouteQuantity for u128 {
+    fn from_u256(value: U256) -> Option<Self> {
+        value.try_into().ok()
+    }
+}
+
+pub fn serialize<T: BloxrouteQuantity, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:#x}", value))
+}
+
+pub fn deserialize<'de, T: BloxrouteQuantity, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let value = U256::deserialize(deserializer)?;
+    T::from_u256(value).ok_or_else(|| D::Error::custom(format!("quantity {} overflows", value)))
+}
+
+pub mod opt {
+    use super::*;
+
+    pub fn serialize<T: BloxrouteQuantity, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T: BloxrouteQuantity, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        match Option::<U256>::deserialize(deserializer)? {
+            Some(value) => T::from_u256(value)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("quantity {} overflows", value))),
+            None => Ok(None),
+        }
+    }
+}
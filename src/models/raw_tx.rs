@@ -0,0 +1,266 @@
+use alloy_primitives::{hex, keccak256, Address, PrimitiveSignature, B256, U256};
+use alloy_rlp::{Decodable, Header, EMPTY_STRING_CODE};
+
+use crate::{
+    error::BloxrouteError,
+    models::transaction::{
+        BloxrouteAccessListItem, BloxrouteAuthorization, BloxrouteEip1559Transaction,
+        BloxrouteEip2930Transaction, BloxrouteEip4844Transaction, BloxrouteEip7702Transaction,
+        BloxrouteLegacyTransaction, BloxrouteTransactionCommon, BloxrouteTransactionContents,
+    },
+};
+
+/// Decodes a hex encoded signed transaction, as sent in `rawTx`.
+pub fn decode_raw_transaction_hex(
+    raw: &str,
+) -> Result<BloxrouteTransactionContents, BloxrouteError> {
+    decode_raw_transaction(&hex::decode(raw)?)
+}
+
+/// Decodes a signed legacy transaction or EIP-2718 envelope, recovering its sender and computing
+/// its hash. EIP-4844 transactions are accepted both with and without their blobs.
+pub fn decode_raw_transaction(raw: &[u8]) -> Result<BloxrouteTransactionContents, BloxrouteError> {
+    let tx_type = match raw.first() {
+        None => return Err(alloy_rlp::Error::InputTooShort.into()),
+        Some(&byte) if byte >= 0xc0 => 0,
+        Some(&byte) => byte,
+    };
+    if tx_type == 0 {
+        return decode_legacy(raw);
+    }
+    if tx_type > 4 {
+        return Err(BloxrouteError::RawTransaction(format!(
+            "unsupported transaction type {:#x}",
+            tx_type
+        )));
+    }
+
+    let mut items = list_items(&raw[1..])?;
+    let mut hashed = raw.to_vec();
+    // Network form of blob transactions: `[tx, blobs, commitments, proofs]`.
+    if tx_type == 3 && items.first().is_some_and(|item| item[0] >= 0xc0) {
+        hashed = [&[tx_type], items[0]].concat();
+        items = list_items(items[0])?;
+    }
+    decode_typed(tx_type, &items, keccak256(&hashed))
+}
+
+fn decode_legacy(raw: &[u8]) -> Result<BloxrouteTransactionContents, BloxrouteError> {
+    let items = list_items(raw)?;
+    let &[nonce, gas_price, gas, to, value, input, v, r, s] = items.as_slice() else {
+        return Err(field_count(0));
+    };
+    let v: u64 = decode(v)?;
+    // EIP-155 signatures commit to the chain id.
+    let (chain_id, odd) = match v {
+        27 | 28 => (None, v == 28),
+        v if v >= 35 => (Some((v - 35) / 2), (v - 35) % 2 == 1),
+        v => {
+            return Err(BloxrouteError::RawTransaction(format!(
+                "invalid signature v {}",
+                v
+            )))
+        }
+    };
+    let chain_id_rlp = chain_id.map(alloy_rlp::encode);
+    let mut payload = items[..6].to_vec();
+    if let Some(chain_id) = &chain_id_rlp {
+        payload.extend([
+            chain_id.as_slice(),
+            &[EMPTY_STRING_CODE],
+            &[EMPTY_STRING_CODE],
+        ]);
+    }
+    let (r, s) = (decode(r)?, decode(s)?);
+    let from = recover(&encode_list(&payload), r, s, odd)?;
+
+    Ok(BloxrouteTransactionContents::Legacy(
+        BloxrouteLegacyTransaction {
+            common: BloxrouteTransactionCommon {
+                hash: Some(keccak256(raw)),
+                from: Some(from),
+                chainId: chain_id,
+                nonce: Some(decode(nonce)?),
+                gas: Some(decode(gas)?),
+                to: decode_to(to)?,
+                value: Some(decode(value)?),
+                input: Some(decode(input)?),
+                r: Some(r),
+                s: Some(s),
+                v: Some(v),
+            },
+            gasPrice: Some(decode(gas_price)?),
+        },
+    ))
+}
+
+/// Decodes the fields of an EIP-2718 transaction:
+/// `[chain_id, nonce, fees.., gas, to, value, input, access_list, extra.., y_parity, r, s]`.
+fn decode_typed(
+    tx_type: u8,
+    items: &[&[u8]],
+    hash: B256,
+) -> Result<BloxrouteTransactionContents, BloxrouteError> {
+    let fees = if tx_type == 1 { 1 } else { 2 };
+    let n = items.len();
+    if n < fees + 10 {
+        return Err(field_count(tx_type));
+    }
+    let y_parity: u64 = decode(items[n - 3])?;
+    let (r, s) = (decode(items[n - 2])?, decode(items[n - 1])?);
+    let payload = [&[tx_type], encode_list(&items[..n - 3]).as_slice()].concat();
+    let from = recover(&payload, r, s, y_parity == 1)?;
+
+    let common = BloxrouteTransactionCommon {
+        hash: Some(hash),
+        from: Some(from),
+        chainId: Some(decode(items[0])?),
+        nonce: Some(decode(items[1])?),
+        gas: Some(decode(items[fees + 2])?),
+        to: decode_to(items[fees + 3])?,
+        value: Some(decode(items[fees + 4])?),
+        input: Some(decode(items[fees + 5])?),
+        r: Some(r),
+        s: Some(s),
+        v: Some(y_parity),
+    };
+    let access_list = Some(decode_access_list(items[fees + 6])?);
+    let y_parity = Some(y_parity);
+
+    let tx = match (tx_type, &items[fees + 7..n - 3]) {
+        (1, []) => BloxrouteTransactionContents::Eip2930(BloxrouteEip2930Transaction {
+            common,
+            gasPrice: Some(decode(items[2])?),
+            accessList: access_list,
+            yParity: y_parity,
+        }),
+        (2, []) => BloxrouteTransactionContents::Eip1559(BloxrouteEip1559Transaction {
+            common,
+            gasPrice: None,
+            maxPriorityFeePerGas: Some(decode(items[2])?),
+            maxFeePerGas: Some(decode(items[3])?),
+            accessList: access_list,
+            yParity: y_parity,
+        }),
+        (3, &[max_fee_per_blob_gas, blob_versioned_hashes]) => {
+            BloxrouteTransactionContents::Eip4844(BloxrouteEip4844Transaction {
+                common,
+                gasPrice: None,
+                maxPriorityFeePerGas: Some(decode(items[2])?),
+                maxFeePerGas: Some(decode(items[3])?),
+                maxFeePerBlobGas: Some(decode(max_fee_per_blob_gas)?),
+                blobVersionedHashes: Some(decode(blob_versioned_hashes)?),
+                accessList: access_list,
+                yParity: y_parity,
+            })
+        }
+        (4, &[authorization_list]) => {
+            BloxrouteTransactionContents::Eip7702(BloxrouteEip7702Transaction {
+                common,
+                gasPrice: None,
+                maxPriorityFeePerGas: Some(decode(items[2])?),
+                maxFeePerGas: Some(decode(items[3])?),
+                authorizationList: Some(decode_authorization_list(authorization_list)?),
+                accessList: access_list,
+                yParity: y_parity,
+            })
+        }
+        _ => return Err(field_count(tx_type)),
+    };
+    Ok(tx)
+}
+
+fn decode_access_list(item: &[u8]) -> Result<Vec<BloxrouteAccessListItem>, BloxrouteError> {
+    list_items(item)?
+        .into_iter()
+        .map(|entry| match list_items(entry)?.as_slice() {
+            &[address, storage_keys] => Ok(BloxrouteAccessListItem {
+                address: decode(address)?,
+                storageKeys: decode(storage_keys)?,
+            }),
+            _ => Err(BloxrouteError::RawTransaction(
+                "invalid access list entry".to_string(),
+            )),
+        })
+        .collect()
+}
+
+fn decode_authorization_list(item: &[u8]) -> Result<Vec<BloxrouteAuthorization>, BloxrouteError> {
+    list_items(item)?
+        .into_iter()
+        .map(|entry| match list_items(entry)?.as_slice() {
+            &[chain_id, address, nonce, y_parity, r, s] => Ok(BloxrouteAuthorization {
+                chainId: decode(chain_id)?,
+                address: decode(address)?,
+                nonce: decode(nonce)?,
+                yParity: decode(y_parity)?,
+                r: decode(r)?,
+                s: decode(s)?,
+            }),
+            _ => Err(BloxrouteError::RawTransaction(
+                "invalid authorization list entry".to_string(),
+            )),
+        })
+        .collect()
+}
+
+/// Splits an RLP list into the encodings of its items.
+fn list_items(mut buf: &[u8]) -> Result<Vec<&[u8]>, BloxrouteError> {
+    let header = Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString.into());
+    }
+    let mut payload = buf
+        .get(..header.payload_length)
+        .ok_or(alloy_rlp::Error::InputTooShort)?;
+    let mut items = vec![];
+    while !payload.is_empty() {
+        let mut rest = payload;
+        let item = Header::decode(&mut rest)?;
+        let len = payload.len() - rest.len() + item.payload_length;
+        let (item, tail) = payload
+            .split_at_checked(len)
+            .ok_or(alloy_rlp::Error::InputTooShort)?;
+        items.push(item);
+        payload = tail;
+    }
+    Ok(items)
+}
+
+fn encode_list(items: &[&[u8]]) -> Vec<u8> {
+    let mut out = vec![];
+    Header {
+        list: true,
+        payload_length: items.iter().map(|item| item.len()).sum(),
+    }
+    .encode(&mut out);
+    for item in items {
+        out.extend_from_slice(item);
+    }
+    out
+}
+
+fn decode<T: Decodable>(mut item: &[u8]) -> Result<T, BloxrouteError> {
+    Ok(T::decode(&mut item)?)
+}
+
+/// `to` is empty for contract creations.
+fn decode_to(item: &[u8]) -> Result<Option<Address>, BloxrouteError> {
+    if item == [EMPTY_STRING_CODE] {
+        return Ok(None);
+    }
+    decode(item).map(Some)
+}
+
+fn recover(payload: &[u8], r: U256, s: U256, odd: bool) -> Result<Address, BloxrouteError> {
+    PrimitiveSignature::new(r, s, odd)
+        .recover_address_from_prehash(&keccak256(payload))
+        .map_err(|err| BloxrouteError::RawTransaction(err.to_string()))
+}
+
+fn field_count(tx_type: u8) -> BloxrouteError {
+    BloxrouteError::RawTransaction(format!(
+        "unexpected number of fields for a type {:#x} transaction",
+        tx_type
+    ))
+}
@@ -1,62 +1,23 @@
 use serde::{Deserialize, Serialize};
 
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumPriceImpact {
-    pub percent: f64,
-    pub infinity: String,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumFee {
-    pub amount: f64,
-    pub mint: String,
-    pub percent: f64,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumQuoteStepProject {
-    pub label: String,
-    pub id: String,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumQuoteStep {
-    pub inToken: String,
-    pub inTokenAddress: String,
-    pub outToken: String,
-    pub outTokenAddress: String,
-    pub inAmount: f64,
-    pub outAmount: f64,
-    pub slippage: f64,
-    pub priceImpactPercent: RaydiumPriceImpact,
-    pub fee: RaydiumFee,
-    pub outAmountMin: f64,
-    pub project: RaydiumQuoteStepProject,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumQuoteRoute {
-    pub inAmount: f64,
-    pub outAmount: f64,
-    pub outAmountMin: f64,
-    pub steps: Vec<RaydiumQuoteStep>,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumQuote {
-    pub inToken: String,
-    pub inTokenAddress: String,
-    pub outToken: String,
-    pub outTokenAddress: String,
-    pub inAmount: f64,
-    pub routes: Vec<RaydiumQuoteRoute>,
-}
+pub use crate::models::solana::TransactionMessage;
+use crate::models::solana::{
+    Fee, PriceImpact, Quote, QuoteRoute, QuoteStep, QuoteStepProject, RouteStep, RouteSwapPayload,
+    RouteSwapResponse, SwapPayload, SwapResponse,
+};
+
+// Kept for compatibility, the swap types are shared with the other projects.
+pub type RaydiumPriceImpact = PriceImpact;
+pub type RaydiumFee = Fee;
+pub type RaydiumQuoteStepProject = QuoteStepProject;
+pub type RaydiumQuoteStep = QuoteStep;
+pub type RaydiumQuoteRoute = QuoteRoute;
+pub type RaydiumQuote = Quote;
+pub type CreateSwapTransactionPayload = SwapPayload;
+pub type CreateSwapTransactionResponse = SwapResponse;
+pub type RaydiumRouteStep = RouteStep;
+pub type CreateRouteSwapPayload = RouteSwapPayload;
+pub type CreateRouteSwapResponse = RouteSwapResponse;
 
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -77,67 +38,6 @@ pub struct RaydiumPools {
     pub pools: Vec<RaydiumPool>,
 }
 
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CreateSwapTransactionPayload {
-    pub ownerAddress: String,
-    pub inToken: String,
-    pub outToken: String,
-    pub inAmount: f64,
-    pub slippage: f64,
-    pub computeLimit: Option<u32>,
-    pub computePrice: Option<u64>,
-    pub tip: Option<u64>,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct TransactionMessage {
-    pub content: String,
-    pub isCleanup: bool,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CreateSwapTransactionResponse {
-    pub outAmount: f64,
-    pub outAmountMin: f64,
-    pub priceImpact: RaydiumPriceImpact,
-    pub fees: Vec<RaydiumFee>,
-    pub transactions: Vec<TransactionMessage>,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RaydiumRouteStep {
-    pub inToken: String,
-    pub outToken: String,
-    pub inAmount: f64,
-    pub outAmount: f64,
-    pub outAmountMin: f64,
-    pub project: RaydiumQuoteStepProject,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CreateRouteSwapPayload {
-    pub ownerAddress: String,
-    pub steps: Vec<RaydiumRouteStep>,
-    pub computeLimit: Option<u32>,
-    pub computePrice: Option<u64>,
-    pub tip: Option<u64>,
-}
-
-#[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CreateRouteSwapResponse {
-    pub outAmount: f64,
-    pub outAmountMin: f64,
-    pub priceImpact: Option<RaydiumPriceImpact>,
-    pub fees: Option<Vec<RaydiumFee>>,
-    pub transactions: Vec<TransactionMessage>,
-}
-
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TraderV2SubmitSignedTransactionPayload {
@@ -0,0 +1,63 @@
+use alloy_primitives::{Address, Bloom, Bytes, B256};
+use serde::Deserialize;
+
+use crate::models::quantity;
+
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteTxReceiptLog {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    #[serde(default, with = "quantity::opt")]
+    pub blockNumber: Option<u64>,
+    pub blockHash: Option<B256>,
+    pub transactionHash: Option<B256>,
+    #[serde(default, with = "quantity::opt")]
+    pub transactionIndex: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub logIndex: Option<u64>,
+    pub removed: Option<bool>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct BloxrouteTxReceipt {
+    pub blockHash: B256,
+    #[serde(with = "quantity")]
+    pub blockNumber: u64,
+    pub transactionHash: B256,
+    #[serde(default, with = "quantity::opt")]
+    pub transactionIndex: Option<u64>,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    /// `1` on success, `0` if the transaction reverted.
+    #[serde(with = "quantity")]
+    pub status: u64,
+    #[serde(with = "quantity")]
+    pub gasUsed: u64,
+    #[serde(default, with = "quantity::opt")]
+    pub cumulativeGasUsed: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub effectiveGasPrice: Option<u128>,
+    /// Set when the transaction deployed a contract.
+    pub contractAddress: Option<Address>,
+    pub logs: Vec<BloxrouteTxReceiptLog>,
+    pub logsBloom: Option<Bloom>,
+    #[serde(default, with = "quantity::opt")]
+    pub r#type: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub blobGasUsed: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub blobGasPrice: Option<u128>,
+    /// Number of transactions in the block.
+    #[serde(default, with = "quantity::opt")]
+    pub txsCount: Option<u64>,
+}
+
+impl BloxrouteTxReceipt {
+    /// Whether the transaction succeeded rather than reverted.
+    pub fn succeeded(&self) -> bool {
+        self.status == 1
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[allow(non_snake_case)]
 #[derive(Deserialize, Debug, Clone)]
@@ -104,3 +104,124 @@ pub struct BloxrouteGetBundleTipStreamResponse {
     pub percentile99: f64,
     pub emaPercentile50: f64,
 }
+
+// Shared by the Trader API swap and order endpoints of every project
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PriceImpact {
+    pub percent: f64,
+    pub infinity: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Fee {
+    pub amount: f64,
+    pub mint: String,
+    pub percent: f64,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteStepProject {
+    pub label: String,
+    pub id: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteStep {
+    pub inToken: String,
+    pub inTokenAddress: String,
+    pub outToken: String,
+    pub outTokenAddress: String,
+    pub inAmount: f64,
+    pub outAmount: f64,
+    pub slippage: f64,
+    pub priceImpactPercent: PriceImpact,
+    pub fee: Fee,
+    pub outAmountMin: f64,
+    pub project: QuoteStepProject,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteRoute {
+    pub inAmount: f64,
+    pub outAmount: f64,
+    pub outAmountMin: f64,
+    pub steps: Vec<QuoteStep>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Quote {
+    pub inToken: String,
+    pub inTokenAddress: String,
+    pub outToken: String,
+    pub outTokenAddress: String,
+    pub inAmount: f64,
+    pub routes: Vec<QuoteRoute>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionMessage {
+    pub content: String,
+    pub isCleanup: bool,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapPayload {
+    pub ownerAddress: String,
+    pub inToken: String,
+    pub outToken: String,
+    pub inAmount: f64,
+    pub slippage: f64,
+    pub computeLimit: Option<u32>,
+    pub computePrice: Option<u64>,
+    pub tip: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapResponse {
+    pub outAmount: f64,
+    pub outAmountMin: f64,
+    pub priceImpact: PriceImpact,
+    pub fees: Vec<Fee>,
+    pub transactions: Vec<TransactionMessage>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RouteStep {
+    pub inToken: String,
+    pub outToken: String,
+    pub inAmount: f64,
+    pub outAmount: f64,
+    pub outAmountMin: f64,
+    pub project: QuoteStepProject,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RouteSwapPayload {
+    pub ownerAddress: String,
+    pub steps: Vec<RouteStep>,
+    pub computeLimit: Option<u32>,
+    pub computePrice: Option<u64>,
+    pub tip: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RouteSwapResponse {
+    pub outAmount: f64,
+    pub outAmountMin: f64,
+    pub priceImpact: Option<PriceImpact>,
+    pub fees: Option<Vec<Fee>>,
+    pub transactions: Vec<TransactionMessage>,
+}
@@ -1,45 +1,367 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{
+    de::{Error, IgnoredAny},
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::value::RawValue;
+
+use crate::{
+    error::BloxrouteError,
+    models::{filter::BloxrouteFilter, quantity, raw_tx::decode_raw_transaction_hex},
+};
 
 #[allow(non_snake_case)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BloxrouteTransactionContents {
-    pub from: Option<String>,
-    pub gas: Option<String>,
-    pub gasPrice: Option<String>,
-    pub hash: Option<String>,
-    pub input: Option<String>,
-    pub maxFeePerGas: Option<String>,
-    pub maxPriorityFeePerGas: Option<String>,
-    pub nonce: Option<String>,
-    pub r: Option<String>,
-    pub s: Option<String>,
-    pub to: Option<String>,
-    pub r#type: Option<String>,
-    pub v: Option<String>,
-    pub value: Option<String>,
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteAccessListItem {
+    pub address: Address,
+    pub storageKeys: Vec<B256>,
+}
+
+/// EIP-7702 authorization to set the code of `authority` to the code at `address`.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteAuthorization {
+    #[serde(with = "quantity")]
+    pub chainId: u64,
+    pub address: Address,
+    #[serde(with = "quantity")]
+    pub nonce: u64,
+    #[serde(with = "quantity")]
+    pub yParity: u64,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// Fields shared by every transaction type. All of them are optional, as the tx stream only
+/// sends the `tx_contents.*` fields listed in `include`.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteTransactionCommon {
+    pub hash: Option<B256>,
+    pub from: Option<Address>,
+    #[serde(default, with = "quantity::opt")]
+    pub chainId: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub nonce: Option<u64>,
+    #[serde(default, with = "quantity::opt")]
+    pub gas: Option<u64>,
+    /// `None` for contract creations.
+    pub to: Option<Address>,
+    pub value: Option<U256>,
+    pub input: Option<Bytes>,
+    pub r: Option<U256>,
+    pub s: Option<U256>,
+    #[serde(default, with = "quantity::opt")]
+    pub v: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteLegacyTransaction {
+    #[serde(flatten)]
+    pub common: BloxrouteTransactionCommon,
+    #[serde(default, with = "quantity::opt")]
+    pub gasPrice: Option<u128>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteEip2930Transaction {
+    #[serde(flatten)]
+    pub common: BloxrouteTransactionCommon,
+    #[serde(default, with = "quantity::opt")]
+    pub gasPrice: Option<u128>,
+    pub accessList: Option<Vec<BloxrouteAccessListItem>>,
+    #[serde(default, with = "quantity::opt")]
+    pub yParity: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteEip1559Transaction {
+    #[serde(flatten)]
+    pub common: BloxrouteTransactionCommon,
+    /// Effective gas price, set once the transaction is mined.
+    #[serde(default, with = "quantity::opt")]
+    pub gasPrice: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxFeePerGas: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxPriorityFeePerGas: Option<u128>,
+    pub accessList: Option<Vec<BloxrouteAccessListItem>>,
+    #[serde(default, with = "quantity::opt")]
+    pub yParity: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteEip4844Transaction {
+    #[serde(flatten)]
+    pub common: BloxrouteTransactionCommon,
+    #[serde(default, with = "quantity::opt")]
+    pub gasPrice: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxFeePerGas: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxPriorityFeePerGas: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxFeePerBlobGas: Option<u128>,
+    pub blobVersionedHashes: Option<Vec<B256>>,
+    pub accessList: Option<Vec<BloxrouteAccessListItem>>,
+    #[serde(default, with = "quantity::opt")]
+    pub yParity: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloxrouteEip7702Transaction {
+    #[serde(flatten)]
+    pub common: BloxrouteTransactionCommon,
+    #[serde(default, with = "quantity::opt")]
+    pub gasPrice: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxFeePerGas: Option<u128>,
+    #[serde(default, with = "quantity::opt")]
+    pub maxPriorityFeePerGas: Option<u128>,
+    pub authorizationList: Option<Vec<BloxrouteAuthorization>>,
+    pub accessList: Option<Vec<BloxrouteAccessListItem>>,
+    #[serde(default, with = "quantity::opt")]
+    pub yParity: Option<u64>,
+}
+
+/// A transaction of any type, tagged by its `type` field.
+///
+/// When `type` was not included, the type is inferred from the fields that are present.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum BloxrouteTransactionContents {
+    #[serde(rename = "0x0")]
+    Legacy(BloxrouteLegacyTransaction),
+    #[serde(rename = "0x1")]
+    Eip2930(BloxrouteEip2930Transaction),
+    #[serde(rename = "0x2")]
+    Eip1559(BloxrouteEip1559Transaction),
+    #[serde(rename = "0x3")]
+    Eip4844(BloxrouteEip4844Transaction),
+    #[serde(rename = "0x4")]
+    Eip7702(BloxrouteEip7702Transaction),
+}
+
+impl BloxrouteTransactionContents {
+    /// EIP-2718 transaction type.
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            BloxrouteTransactionContents::Legacy(_) => 0,
+            BloxrouteTransactionContents::Eip2930(_) => 1,
+            BloxrouteTransactionContents::Eip1559(_) => 2,
+            BloxrouteTransactionContents::Eip4844(_) => 3,
+            BloxrouteTransactionContents::Eip7702(_) => 4,
+        }
+    }
+
+    pub fn common(&self) -> &BloxrouteTransactionCommon {
+        match self {
+            BloxrouteTransactionContents::Legacy(tx) => &tx.common,
+            BloxrouteTransactionContents::Eip2930(tx) => &tx.common,
+            BloxrouteTransactionContents::Eip1559(tx) => &tx.common,
+            BloxrouteTransactionContents::Eip4844(tx) => &tx.common,
+            BloxrouteTransactionContents::Eip7702(tx) => &tx.common,
+        }
+    }
+}
+
+/// The fields that tell the transaction types apart, read ahead of the transaction itself.
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct BloxrouteTransactionTypeTag {
+    #[serde(default, rename = "type", with = "quantity::opt")]
+    txType: Option<u64>,
+    authorizationList: Option<IgnoredAny>,
+    blobVersionedHashes: Option<IgnoredAny>,
+    maxFeePerBlobGas: Option<IgnoredAny>,
+    maxFeePerGas: Option<IgnoredAny>,
+    maxPriorityFeePerGas: Option<IgnoredAny>,
+    accessList: Option<IgnoredAny>,
+}
+
+impl BloxrouteTransactionTypeTag {
+    /// The `type` field, or the type implied by the fields present when it is missing.
+    fn tx_type(&self) -> u64 {
+        match self.txType {
+            Some(tx_type) => tx_type,
+            None if self.authorizationList.is_some() => 4,
+            None if self.blobVersionedHashes.is_some() || self.maxFeePerBlobGas.is_some() => 3,
+            None if self.maxFeePerGas.is_some() || self.maxPriorityFeePerGas.is_some() => 2,
+            None if self.accessList.is_some() => 1,
+            None => 0,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BloxrouteTransactionContents {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let tag: BloxrouteTransactionTypeTag =
+            serde_json::from_str(raw.get()).map_err(D::Error::custom)?;
+        let tx = match tag.tx_type() {
+            0 => serde_json::from_str(raw.get()).map(BloxrouteTransactionContents::Legacy),
+            1 => serde_json::from_str(raw.get()).map(BloxrouteTransactionContents::Eip2930),
+            2 => serde_json::from_str(raw.get()).map(BloxrouteTransactionContents::Eip1559),
+            3 => serde_json::from_str(raw.get()).map(BloxrouteTransactionContents::Eip4844),
+            4 => serde_json::from_str(raw.get()).map(BloxrouteTransactionContents::Eip7702),
+            other => {
+                return Err(D::Error::custom(format!(
+                    "unsupported transaction type {:#x}",
+                    other
+                )))
+            }
+        };
+        tx.map_err(D::Error::custom)
+    }
 }
 
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BloxrouteTransaction {
-    pub txHash: String,
+    pub txHash: B256,
     pub txContents: Option<BloxrouteTransactionContents>,
     pub localRegion: Option<bool>,
     pub rawTx: Option<String>,
 }
 
+impl BloxrouteTransaction {
+    /// Decodes `rawTx`, included when subscribing with `raw_tx`, into the full transaction.
+    pub fn decode_raw_tx(&self) -> Result<BloxrouteTransactionContents, BloxrouteError> {
+        let raw = self.rawTx.as_deref().ok_or_else(|| {
+            BloxrouteError::RawTransaction("`raw_tx` was not included".to_string())
+        })?;
+        decode_raw_transaction_hex(raw)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BloxrouteTransactionRequestInclude {
     pub include: Option<Vec<String>>,
     pub duplicates: Option<bool>,
     pub include_from_blockchain: Option<bool>,
-    pub filters: Option<String>,
+    pub filters: Option<BloxrouteFilter>,
     pub blockchain_network: Option<String>,
 }
 
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BloxrouteBlockRequestInclude {
     pub include: Option<Vec<String>>,
     pub blockchain_network: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteTransactionStatusRequest {
+    pub include: Option<Vec<String>>,
+    /// Hashes of the transactions to follow.
+    pub transactions: Vec<String>,
+    pub blockchain_network: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BloxrouteTransactionStatusKind {
+    Pending,
+    Mined,
+    Dropped,
+    Replaced,
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteTransactionStatus {
+    pub txHash: B256,
+    pub status: BloxrouteTransactionStatusKind,
+    /// Set once the transaction is mined.
+    pub blockHash: Option<B256>,
+    #[serde(default, with = "quantity::opt")]
+    pub blockNumber: Option<u64>,
+    /// Hash of the transaction that replaced this one.
+    pub replacedBy: Option<B256>,
+}
+
+/// Params of `blxr_tx`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteSendTransactionRequest {
+    /// Raw signed transaction, hex encoded without the `0x` prefix.
+    pub transaction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce_monitoring: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+    /// BSC only: only send to validators.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validators_only: Option<bool>,
+    /// BSC only: send to the next validator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_validator: Option<bool>,
+    /// Milliseconds to wait for `next_validator` before falling back to the network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fall_back: Option<u64>,
+}
+
+/// Params of `blxr_batch_tx`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteSendBatchTransactionsRequest {
+    /// Raw signed transactions, hex encoded without the `0x` prefix.
+    pub transactions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce_monitoring: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validators_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_validator: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fall_back: Option<u64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteSendTransactionResponse {
+    pub txHash: B256,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BloxrouteSendBatchTransactionsResponse {
+    pub txHashes: Vec<B256>,
+}
+
+/// Params of `blxr_private_tx`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteSendPrivateTransactionRequest {
+    /// Raw signed transaction, hex encoded without the `0x` prefix.
+    pub transaction: String,
+    /// Seconds after which the transaction is sent publicly if it was not included privately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Builder name -> signature, e.g. `{"all": ""}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mev_builders: Option<HashMap<String, String>>,
+    /// Only lets builders include the transaction in bundles that do not front-run it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub front_running_protection: Option<bool>,
+    /// Address receiving the backrun refunds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_recipient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+}
+
+/// Params of `blxr_private_tx_cancel`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BloxrouteCancelPrivateTransactionRequest {
+    pub transaction_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_network: Option<String>,
+}
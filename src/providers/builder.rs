@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use crate::{
+    error::BloxrouteError,
+    providers::{http::BloxrouteHttpClient, ws::BloxrouteWsClient},
+};
+
+/// Default time to wait for a response, in milliseconds.
+pub const DEFAULT_REQUEST_TIMEOUT: u64 = 10_000;
+
+/// Default capacity of the channel behind each WS stream.
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 1000;
+
+/// Default interval of the WS keepalive pings, in seconds.
+pub const DEFAULT_PING_INTERVAL: u64 = 30;
+
+/// How the WS client retries (re)connecting to the gateway.
+#[derive(Clone, Debug)]
+pub struct BloxrouteReconnectPolicy {
+    /// Delay before the first retry, doubled after every failed attempt.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Number of failed attempts after which the client gives up, `None` retries forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for BloxrouteReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BloxrouteTlsOptions {
+    /// PEM encoded certificates trusted in addition to the system roots.
+    pub root_certificates: Vec<Vec<u8>>,
+    /// Accepts any server certificate. Only meant for local test gateways.
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// Configuration shared by the HTTP and WS clients.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), bloxroute_sdk::error::BloxrouteError> {
+/// use bloxroute_sdk::providers::builder::BloxrouteClientBuilder;
+/// use std::time::Duration;
+///
+/// let client = BloxrouteClientBuilder::new("wss://germany.eth.blxrbdn.com/ws", "AUTH_HEADER")
+///     .request_timeout(Duration::from_secs(5))
+///     .channel_capacity(10_000)
+///     .connect_ws()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BloxrouteClientBuilder {
+    pub endpoint: String,
+    pub auth_header: String,
+    /// HTTP request timeout, and how long WS requests wait for the gateway's response.
+    pub request_timeout: Duration,
+    /// Timeout of the TCP, TLS and WS handshakes. `None` waits as long as the OS does.
+    pub connect_timeout: Option<Duration>,
+    pub reconnect_policy: BloxrouteReconnectPolicy,
+    /// How long the WS client waits for a frame before pinging the gateway. Without an answer
+    /// within another interval the connection is considered dead and reopened. `None` disables
+    /// the keepalive.
+    pub ping_interval: Option<Duration>,
+    /// Messages buffered per WS stream, further ones are dropped until the stream catches up.
+    pub channel_capacity: usize,
+    pub tls: BloxrouteTlsOptions,
+    pub user_agent: Option<String>,
+    /// `http://host:port` proxy. The WS client tunnels through it with `CONNECT`.
+    pub proxy: Option<String>,
+}
+
+impl BloxrouteClientBuilder {
+    pub fn new(endpoint: impl Into<String>, auth_header: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            auth_header: auth_header.into(),
+            request_timeout: Duration::from_millis(DEFAULT_REQUEST_TIMEOUT),
+            connect_timeout: None,
+            reconnect_policy: BloxrouteReconnectPolicy::default(),
+            ping_interval: Some(Duration::from_secs(DEFAULT_PING_INTERVAL)),
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            tls: BloxrouteTlsOptions::default(),
+            user_agent: None,
+            proxy: None,
+        }
+    }
+
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn reconnect_policy(mut self, policy: BloxrouteReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn tls(mut self, tls: BloxrouteTlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    pub fn build_http(self) -> Result<BloxrouteHttpClient, BloxrouteError> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.request_timeout)
+            .danger_accept_invalid_certs(self.tls.danger_accept_invalid_certs);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        for pem in &self.tls.root_certificates {
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(pem)?);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        Ok(BloxrouteHttpClient {
+            endpoint: self.endpoint,
+            auth_header: self.auth_header,
+            client: builder.build()?,
+        })
+    }
+
+    pub async fn connect_ws(self) -> Result<BloxrouteWsClient, BloxrouteError> {
+        BloxrouteWsClient::connect_with(self).await
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+
+use futures_util::{future::join_all, SinkExt};
+use tokio::time;
+
+use crate::{
+    error::BloxrouteError,
+    providers::{builder::BloxrouteClientBuilder, ws::open_socket},
+};
+
+/// Cloud API regions serving Ethereum and BSC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BloxrouteEvmRegion {
+    Virginia,
+    Uk,
+    Singapore,
+    Germany,
+}
+
+impl BloxrouteEvmRegion {
+    pub const ALL: [BloxrouteEvmRegion; 4] = [
+        BloxrouteEvmRegion::Virginia,
+        BloxrouteEvmRegion::Uk,
+        BloxrouteEvmRegion::Singapore,
+        BloxrouteEvmRegion::Germany,
+    ];
+
+    fn host(&self) -> &'static str {
+        match self {
+            BloxrouteEvmRegion::Virginia => "virginia",
+            BloxrouteEvmRegion::Uk => "uk",
+            BloxrouteEvmRegion::Singapore => "singapore",
+            BloxrouteEvmRegion::Germany => "germany",
+        }
+    }
+}
+
+/// Trader API regions serving Solana.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BloxrouteSolanaRegion {
+    NewYork,
+    LosAngeles,
+    Uk,
+    Amsterdam,
+    Germany,
+    Tokyo,
+}
+
+impl BloxrouteSolanaRegion {
+    pub const ALL: [BloxrouteSolanaRegion; 6] = [
+        BloxrouteSolanaRegion::NewYork,
+        BloxrouteSolanaRegion::LosAngeles,
+        BloxrouteSolanaRegion::Uk,
+        BloxrouteSolanaRegion::Amsterdam,
+        BloxrouteSolanaRegion::Germany,
+        BloxrouteSolanaRegion::Tokyo,
+    ];
+
+    fn host(&self) -> &'static str {
+        match self {
+            BloxrouteSolanaRegion::NewYork => "ny",
+            BloxrouteSolanaRegion::LosAngeles => "la",
+            BloxrouteSolanaRegion::Uk => "uk",
+            BloxrouteSolanaRegion::Amsterdam => "amsterdam",
+            BloxrouteSolanaRegion::Germany => "germany",
+            BloxrouteSolanaRegion::Tokyo => "tokyo",
+        }
+    }
+}
+
+/// A gateway to connect to: a known region of a network, or any other URL.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BloxrouteEndpoint {
+    Ethereum(BloxrouteEvmRegion),
+    Bsc(BloxrouteEvmRegion),
+    Solana(BloxrouteSolanaRegion),
+    /// Used as is for both clients, e.g. a local stand-in gateway.
+    Custom(String),
+}
+
+impl BloxrouteEndpoint {
+    pub fn ethereum() -> Vec<BloxrouteEndpoint> {
+        BloxrouteEvmRegion::ALL
+            .into_iter()
+            .map(BloxrouteEndpoint::Ethereum)
+            .collect()
+    }
+
+    pub fn bsc() -> Vec<BloxrouteEndpoint> {
+        BloxrouteEvmRegion::ALL
+            .into_iter()
+            .map(BloxrouteEndpoint::Bsc)
+            .collect()
+    }
+
+    pub fn solana() -> Vec<BloxrouteEndpoint> {
+        BloxrouteSolanaRegion::ALL
+            .into_iter()
+            .map(BloxrouteEndpoint::Solana)
+            .collect()
+    }
+
+    /// URL to give `BloxrouteWsClient`.
+    pub fn ws_url(&self) -> String {
+        match self {
+            BloxrouteEndpoint::Ethereum(region) => {
+                format!("wss://{}.eth.blxrbdn.com/ws", region.host())
+            }
+            BloxrouteEndpoint::Bsc(region) => format!("wss://{}.bsc.blxrbdn.com/ws", region.host()),
+            BloxrouteEndpoint::Solana(region) => {
+                format!("wss://{}.solana.dex.blxrbdn.com/ws", region.host())
+            }
+            BloxrouteEndpoint::Custom(url) => url.clone(),
+        }
+    }
+
+    /// URL to give `BloxrouteHttpClient`. EVM networks share the global Cloud API endpoint.
+    pub fn http_url(&self) -> String {
+        match self {
+            BloxrouteEndpoint::Ethereum(_) | BloxrouteEndpoint::Bsc(_) => {
+                "https://api.blxrbdn.com".to_string()
+            }
+            BloxrouteEndpoint::Solana(region) => {
+                format!("https://{}.solana.dex.blxrbdn.com", region.host())
+            }
+            BloxrouteEndpoint::Custom(url) => url.clone(),
+        }
+    }
+}
+
+/// Result of probing an endpoint.
+#[derive(Clone, Debug)]
+pub struct BloxrouteEndpointLatency {
+    pub endpoint: BloxrouteEndpoint,
+    /// Time taken by the WS handshake, including TCP and TLS.
+    pub latency: Duration,
+}
+
+/// Measures how long the WS handshake with the endpoint takes, using the config for everything
+/// but the URL. Gives up after the connect timeout, or the request timeout if there is none.
+pub async fn probe_endpoint(
+    endpoint: &BloxrouteEndpoint,
+    config: &BloxrouteClientBuilder,
+) -> Result<Duration, BloxrouteError> {
+    let mut config = config.clone();
+    config.endpoint = endpoint.ws_url();
+    let timeout = config.connect_timeout.unwrap_or(config.request_timeout);
+
+    let start = Instant::now();
+    let (mut write, _read) = time::timeout(timeout, open_socket(&config))
+        .await
+        .map_err(|_| BloxrouteError::Timeout)??;
+    let latency = start.elapsed();
+    let _ = write.close().await;
+    Ok(latency)
+}
+
+/// Probes the candidates concurrently and returns the one with the fastest handshake.
+///
+/// Fails with the last error if none of them could be reached.
+pub async fn select_fastest_endpoint(
+    candidates: &[BloxrouteEndpoint],
+    config: &BloxrouteClientBuilder,
+) -> Result<BloxrouteEndpointLatency, BloxrouteError> {
+    let probes = candidates
+        .iter()
+        .map(|endpoint| async move { (endpoint, probe_endpoint(endpoint, config).await) });
+
+    let mut fastest: Option<BloxrouteEndpointLatency> = None;
+    let mut last_err = BloxrouteError::ConnectionClosed;
+    for (endpoint, result) in join_all(probes).await {
+        match result {
+            Ok(latency) if fastest.as_ref().map_or(true, |f| latency < f.latency) => {
+                fastest = Some(BloxrouteEndpointLatency {
+                    endpoint: endpoint.clone(),
+                    latency,
+                });
+            }
+            Ok(_) => {}
+            Err(err) => {
+                println!("Error probing {}: {}", endpoint.ws_url(), err);
+                last_err = err;
+            }
+        }
+    }
+    fastest.ok_or(last_err)
+}
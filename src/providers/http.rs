@@ -1,17 +1,42 @@
-use serde_json::json;
-use std::error::Error;
-
-use crate::models::{
-    raydium::{
-        CreateRouteSwapPayload, CreateRouteSwapResponse, CreateSwapTransactionPayload,
-        CreateSwapTransactionResponse, RaydiumPool, RaydiumPools, RaydiumQuote,
-        TraderV2SubmitSignedTransactionBatchPayload, TraderV2SubmitSignedTransactionBatchResponse,
-        TraderV2SubmitSignedTransactionPayload, TraderV2SubmitSignedTransactionResponse,
-    },
-    solana::{
-        BloxrouteGetAccountBalance, BloxrouteGetAccountBalanceResponse,
-        BloxrouteGetRateLimitResponse, BloxrouteGetStreamPriorityFee,
-        BloxrouteGetTransactionStatusResponse,
+use alloy_primitives::B256;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Serialize,
+};
+
+use crate::{
+    error::BloxrouteError,
+    models::{
+        bundle::{
+            BloxrouteBundle, BloxrouteBundleSimulation, BloxrouteSimulateBundleRequest,
+            BloxrouteSubmitBundleResponse,
+        },
+        jupiter::JupiterSwapInstructionsResponse,
+        openbook::{
+            BloxrouteOpenbookCancelOrderPayload, BloxrouteOpenbookGetOpenOrdersResponse,
+            BloxrouteOpenbookGetUnsettledResponse, BloxrouteOpenbookOpenOrder,
+            BloxrouteOpenbookOrderPayload, BloxrouteOpenbookOrderResponse,
+            BloxrouteOpenbookSettlePayload, BloxrouteOpenbookTransactionsResponse,
+        },
+        raydium::{
+            CreateRouteSwapPayload, CreateRouteSwapResponse, CreateSwapTransactionPayload,
+            CreateSwapTransactionResponse, RaydiumPool, RaydiumPools, RaydiumQuote,
+            TraderV2SubmitSignedTransactionBatchPayload,
+            TraderV2SubmitSignedTransactionBatchResponse, TraderV2SubmitSignedTransactionPayload,
+            TraderV2SubmitSignedTransactionResponse,
+        },
+        solana::{
+            BloxrouteGetAccountBalance, BloxrouteGetAccountBalanceResponse,
+            BloxrouteGetRateLimitResponse, BloxrouteGetStreamPriorityFee,
+            BloxrouteGetTransactionStatusResponse, Quote, RouteSwapPayload, RouteSwapResponse,
+            SwapPayload, SwapResponse, TransactionMessage,
+        },
+        transaction::{
+            BloxrouteCancelPrivateTransactionRequest, BloxrouteSendBatchTransactionsRequest,
+            BloxrouteSendBatchTransactionsResponse, BloxrouteSendPrivateTransactionRequest,
+            BloxrouteSendTransactionRequest, BloxrouteSendTransactionResponse,
+        },
+        BloxrouteJsonRpcResponse, BloxrouteRequestParams, BloxrouteRequestPayload,
     },
 };
 
@@ -24,56 +49,159 @@ pub struct BloxrouteHttpClient {
 
 impl BloxrouteHttpClient {
     pub fn new(endpoint: String, auth_header: String) -> BloxrouteHttpClient {
-        let client = Self {
+        Self {
             auth_header,
             endpoint,
             client: reqwest::Client::new(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, endpoint: String) -> Result<T, BloxrouteError> {
+        let request = self.client.get(endpoint);
+        self.send(request).await
+    }
+
+    async fn post<T: DeserializeOwned, P: Serialize>(
+        &self,
+        endpoint: String,
+        payload: &P,
+    ) -> Result<T, BloxrouteError> {
+        let request = self.client.post(endpoint).json(payload);
+        self.send(request).await
+    }
+
+    /// Sends an authenticated request and decodes the JSON response, mapping failures to errors.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, BloxrouteError> {
+        let response = self.send_text(request).await?;
+        serde_json::from_str(response.as_str())
+            .map_err(|err| BloxrouteError::deserialization(err, &response))
+    }
+
+    /// Sends an authenticated request and returns the body of a successful response.
+    async fn send_text(&self, request: reqwest::RequestBuilder) -> Result<String, BloxrouteError> {
+        let resp = request
+            .header("Authorization", self.auth_header.clone())
+            .send()
+            .await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let response = resp.text().await?;
+        if !status.is_success() {
+            return Err(BloxrouteError::from_status(status, &headers, response));
+        }
+        Ok(response)
+    }
+
+    /// Sends a JSON-RPC request to the endpoint and returns its result.
+    async fn rpc<T: DeserializeOwned, P: Serialize>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<T, BloxrouteError> {
+        let endpoint = self.endpoint.clone();
+        let payload = BloxrouteRequestPayload {
+            id: "1".to_string(),
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: BloxrouteRequestParams::Object(params),
         };
-        return client;
+        let body = self
+            .send_text(self.client.post(endpoint).json(&payload))
+            .await?;
+        let response: BloxrouteJsonRpcResponse<T> = serde_json::from_str(body.as_str())
+            .map_err(|err| BloxrouteError::deserialization(err, &body))?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(error.into()),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(BloxrouteError::deserialization(
+                serde::de::Error::missing_field("result"),
+                &body,
+            )),
+        }
+    }
+
+    // EVM
+    /// Sends a signed transaction through the BDN (`blxr_tx`) and returns its hash.
+    pub async fn send_transaction(
+        &self,
+        params: BloxrouteSendTransactionRequest,
+    ) -> Result<B256, BloxrouteError> {
+        let response_json: BloxrouteSendTransactionResponse = self.rpc("blxr_tx", params).await?;
+        Ok(response_json.txHash)
+    }
+
+    /// Sends several signed transactions through the BDN (`blxr_batch_tx`) and returns their hashes.
+    pub async fn send_batch_transactions(
+        &self,
+        params: BloxrouteSendBatchTransactionsRequest,
+    ) -> Result<Vec<B256>, BloxrouteError> {
+        let response_json: BloxrouteSendBatchTransactionsResponse =
+            self.rpc("blxr_batch_tx", params).await?;
+        Ok(response_json.txHashes)
+    }
+
+    /// Sends a transaction to MEV builders only (`blxr_private_tx`) and returns its hash.
+    pub async fn send_private_transaction(
+        &self,
+        params: BloxrouteSendPrivateTransactionRequest,
+    ) -> Result<B256, BloxrouteError> {
+        let response_json: BloxrouteSendTransactionResponse =
+            self.rpc("blxr_private_tx", params).await?;
+        Ok(response_json.txHash)
+    }
+
+    /// Withdraws a private transaction from the builders (`blxr_private_tx_cancel`).
+    pub async fn cancel_private_transaction(
+        &self,
+        params: BloxrouteCancelPrivateTransactionRequest,
+    ) -> Result<(), BloxrouteError> {
+        let _: IgnoredAny = self.rpc("blxr_private_tx_cancel", params).await?;
+        Ok(())
+    }
+
+    /// Submits a bundle to MEV builders (`blxr_submit_bundle`) and returns its hash.
+    pub async fn submit_bundle(&self, bundle: BloxrouteBundle) -> Result<String, BloxrouteError> {
+        let response_json: BloxrouteSubmitBundleResponse =
+            self.rpc("blxr_submit_bundle", bundle).await?;
+        Ok(response_json.bundleHash)
     }
 
+    /// Simulates a bundle (`blxr_simulate_bundle`) without submitting it.
+    pub async fn simulate_bundle(
+        &self,
+        params: BloxrouteSimulateBundleRequest,
+    ) -> Result<BloxrouteBundleSimulation, BloxrouteError> {
+        self.rpc("blxr_simulate_bundle", params).await
+    }
+
+    // Solana
     pub async fn get_raydium_quotes(
         &self,
         token_in: String,
         token_out: String,
         amount_in: f64,
         slippage: f64,
-    ) -> Result<RaydiumQuote, Box<dyn Error + Send + Sync>> {
+    ) -> Result<RaydiumQuote, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/raydium/quotes?inToken={token_in}&outToken={token_out}&inAmount={amount_in}&slippage={slippage}");
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: RaydiumQuote = serde_json::from_str(response.as_str())?;
+        let response_json: RaydiumQuote = self.get(endpoint).await?;
         Ok(response_json)
     }
 
-    pub async fn get_raydium_pools(
-        &self,
-    ) -> Result<Vec<RaydiumPool>, Box<dyn Error + Send + Sync>> {
+    pub async fn get_raydium_pools(&self) -> Result<Vec<RaydiumPool>, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/raydium/pools");
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: RaydiumPools = serde_json::from_str(response.as_str())?;
+        let response_json: RaydiumPools = self.get(endpoint).await?;
         Ok(response_json.pools)
     }
 
     pub async fn get_raydium_pool_reserves(
         &self,
         pairs: Vec<String>,
-    ) -> Result<Vec<RaydiumPool>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Vec<RaydiumPool>, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!(
             "{_endpoint}/api/v2/raydium/pool-reserves?{}",
@@ -83,92 +211,162 @@ impl BloxrouteHttpClient {
                 .collect::<Vec<String>>()
                 .join("&")
         );
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: RaydiumPools = serde_json::from_str(response.as_str())?;
+        let response_json: RaydiumPools = self.get(endpoint).await?;
         Ok(response_json.pools)
     }
 
     pub async fn create_raydium_swap_transaction(
         &self,
         payload: CreateSwapTransactionPayload,
-    ) -> Result<CreateSwapTransactionResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<CreateSwapTransactionResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/raydium/swap");
-        let resp = self
-            .client
-            .clone()
-            .post(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .json(&json!(payload))
-            .send()
-            .await?;
-
-        let response = resp.text().await.unwrap();
-        let response_json: CreateSwapTransactionResponse = serde_json::from_str(response.as_str())?;
+        let response_json: CreateSwapTransactionResponse = self.post(endpoint, &payload).await?;
         Ok(response_json)
     }
 
     pub async fn create_raydium_route_swap(
         &self,
         payload: CreateRouteSwapPayload,
-    ) -> Result<CreateRouteSwapResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<CreateRouteSwapResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/raydium/route-swap");
-        let resp = self
-            .client
-            .clone()
-            .post(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .json(&json!(payload))
-            .send()
-            .await?;
+        let response_json: CreateRouteSwapResponse = self.post(endpoint, &payload).await?;
+        Ok(response_json)
+    }
+
+    pub async fn get_jupiter_quotes(
+        &self,
+        token_in: String,
+        token_out: String,
+        amount_in: f64,
+        slippage: f64,
+    ) -> Result<Quote, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/jupiter/quotes?inToken={token_in}&outToken={token_out}&inAmount={amount_in}&slippage={slippage}");
+        let response_json: Quote = self.get(endpoint).await?;
+        Ok(response_json)
+    }
+
+    pub async fn create_jupiter_swap_transaction(
+        &self,
+        payload: SwapPayload,
+    ) -> Result<SwapResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/jupiter/swap");
+        let response_json: SwapResponse = self.post(endpoint, &payload).await?;
+        Ok(response_json)
+    }
+
+    /// Same as `create_jupiter_swap_transaction`, returning the instructions instead of a built
+    /// transaction.
+    pub async fn create_jupiter_swap_instructions(
+        &self,
+        payload: SwapPayload,
+    ) -> Result<JupiterSwapInstructionsResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/jupiter/swap-instructions");
+        let response_json: JupiterSwapInstructionsResponse = self.post(endpoint, &payload).await?;
+        Ok(response_json)
+    }
+
+    pub async fn create_jupiter_route_swap(
+        &self,
+        payload: RouteSwapPayload,
+    ) -> Result<RouteSwapResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/jupiter/route-swap");
+        let response_json: RouteSwapResponse = self.post(endpoint, &payload).await?;
+        Ok(response_json)
+    }
+
+    pub async fn get_openbook_open_orders(
+        &self,
+        market: String,
+        owner: String,
+        limit: Option<u64>,
+    ) -> Result<Vec<BloxrouteOpenbookOpenOrder>, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!(
+            "{_endpoint}/api/v2/openbook/open-orders/{market}?address={owner}{}",
+            match limit {
+                Some(limit) => format!("&limit={limit}"),
+                None => "".to_string(),
+            }
+        );
+        let response_json: BloxrouteOpenbookGetOpenOrdersResponse = self.get(endpoint).await?;
+        Ok(response_json.orders)
+    }
 
-        let response = resp.text().await.unwrap();
-        let response_json: CreateRouteSwapResponse = serde_json::from_str(response.as_str())?;
+    pub async fn get_openbook_unsettled(
+        &self,
+        market: String,
+        owner: String,
+    ) -> Result<BloxrouteOpenbookGetUnsettledResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint =
+            format!("{_endpoint}/api/v2/openbook/unsettled/{market}?ownerAddress={owner}");
+        let response_json: BloxrouteOpenbookGetUnsettledResponse = self.get(endpoint).await?;
+        Ok(response_json)
+    }
+
+    pub async fn create_openbook_order(
+        &self,
+        payload: BloxrouteOpenbookOrderPayload,
+    ) -> Result<BloxrouteOpenbookOrderResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/openbook/place");
+        let response_json: BloxrouteOpenbookOrderResponse = self.post(endpoint, &payload).await?;
+        Ok(response_json)
+    }
+
+    pub async fn create_openbook_replace_order(
+        &self,
+        payload: BloxrouteOpenbookOrderPayload,
+    ) -> Result<BloxrouteOpenbookOrderResponse, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/openbook/replace");
+        let response_json: BloxrouteOpenbookOrderResponse = self.post(endpoint, &payload).await?;
         Ok(response_json)
     }
 
+    pub async fn create_openbook_cancel_order(
+        &self,
+        payload: BloxrouteOpenbookCancelOrderPayload,
+    ) -> Result<Vec<TransactionMessage>, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/openbook/cancel");
+        let response_json: BloxrouteOpenbookTransactionsResponse =
+            self.post(endpoint, &payload).await?;
+        Ok(response_json.transactions)
+    }
+
+    pub async fn create_openbook_settle(
+        &self,
+        payload: BloxrouteOpenbookSettlePayload,
+    ) -> Result<Vec<TransactionMessage>, BloxrouteError> {
+        let _endpoint = &self.endpoint;
+        let endpoint = format!("{_endpoint}/api/v2/openbook/settle");
+        let response_json: BloxrouteOpenbookTransactionsResponse =
+            self.post(endpoint, &payload).await?;
+        Ok(response_json.transactions)
+    }
+
     // General
     pub async fn get_account_balance(
         &self,
         owner_address: String,
-    ) -> Result<Vec<BloxrouteGetAccountBalance>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Vec<BloxrouteGetAccountBalance>, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/balance?ownerAddress={owner_address}");
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: BloxrouteGetAccountBalanceResponse =
-            serde_json::from_str(response.as_str())?;
+        let response_json: BloxrouteGetAccountBalanceResponse = self.get(endpoint).await?;
         Ok(response_json.tokens)
     }
 
-    pub async fn get_rate_limit(
-        &self,
-    ) -> Result<BloxrouteGetRateLimitResponse, Box<dyn Error + Send + Sync>> {
+    pub async fn get_rate_limit(&self) -> Result<BloxrouteGetRateLimitResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/rate-limit");
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: BloxrouteGetRateLimitResponse = serde_json::from_str(response.as_str())?;
+        let response_json: BloxrouteGetRateLimitResponse = self.get(endpoint).await?;
         Ok(response_json)
     }
 
@@ -176,86 +374,48 @@ impl BloxrouteHttpClient {
         &self,
         project: String,
         percentile: Option<f64>,
-    ) -> Result<BloxrouteGetStreamPriorityFee, Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteGetStreamPriorityFee, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!(
             "{_endpoint}/api/v2/system/priority-fee?project={project}{}",
-            if percentile.is_some() {
-                format!("&{}", percentile.unwrap())
-            } else {
-                "".to_string()
+            match percentile {
+                Some(percentile) => format!("&percentile={percentile}"),
+                None => "".to_string(),
             }
         );
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: BloxrouteGetStreamPriorityFee = serde_json::from_str(response.as_str())?;
+        let response_json: BloxrouteGetStreamPriorityFee = self.get(endpoint).await?;
         Ok(response_json)
     }
 
     pub async fn get_transaction_status(
         &self,
         signature: String,
-    ) -> Result<BloxrouteGetTransactionStatusResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteGetTransactionStatusResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/transaction?signature={signature}");
-        let resp = self
-            .client
-            .clone()
-            .get(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .send()
-            .await?;
-        let response = resp.text().await?;
-        let response_json: BloxrouteGetTransactionStatusResponse =
-            serde_json::from_str(response.as_str())?;
+        let response_json: BloxrouteGetTransactionStatusResponse = self.get(endpoint).await?;
         Ok(response_json)
     }
 
     pub async fn submit_signed_tx(
         &self,
         payload: TraderV2SubmitSignedTransactionPayload,
-    ) -> Result<TraderV2SubmitSignedTransactionResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<TraderV2SubmitSignedTransactionResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/submit");
-        let resp = self
-            .client
-            .clone()
-            .post(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .json(&json!(payload))
-            .send()
-            .await?;
-
-        let response = resp.text().await.unwrap();
         let response_json: TraderV2SubmitSignedTransactionResponse =
-            serde_json::from_str(response.as_str())?;
+            self.post(endpoint, &payload).await?;
         Ok(response_json)
     }
 
     pub async fn submit_signed_tx_batch(
         &self,
         payload: TraderV2SubmitSignedTransactionBatchPayload,
-    ) -> Result<TraderV2SubmitSignedTransactionBatchResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<TraderV2SubmitSignedTransactionBatchResponse, BloxrouteError> {
         let _endpoint = &self.endpoint;
         let endpoint = format!("{_endpoint}/api/v2/submit-batch");
-        let resp = self
-            .client
-            .clone()
-            .post(endpoint)
-            .header("Authorization", self.auth_header.clone())
-            .json(&json!(payload))
-            .send()
-            .await?;
-
-        let response = resp.text().await.unwrap();
         let response_json: TraderV2SubmitSignedTransactionBatchResponse =
-            serde_json::from_str(response.as_str())?;
+            self.post(endpoint, &payload).await?;
         Ok(response_json)
     }
 }
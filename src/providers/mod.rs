@@ -1,2 +1,6 @@
+pub mod builder;
+pub mod endpoint;
 pub mod http;
+pub mod multi;
+pub mod stream;
 pub mod ws;
@@ -0,0 +1,332 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::B256;
+use futures_util::{future::join_all, Stream, StreamExt};
+use tokio::{
+    sync::mpsc::{self, Receiver},
+    task::JoinHandle,
+    time,
+};
+
+use crate::{
+    error::BloxrouteError,
+    models::{
+        block::BloxrouteBlock,
+        transaction::{
+            BloxrouteBlockRequestInclude, BloxrouteTransaction, BloxrouteTransactionRequestInclude,
+        },
+    },
+    providers::{
+        builder::BloxrouteClientBuilder,
+        stream::{BloxrouteStream, BloxrouteStreamItem},
+        ws::BloxrouteWsClient,
+    },
+};
+
+/// Default number of recent keys remembered to drop duplicates.
+pub const DEFAULT_DEDUP_CAPACITY: usize = 10_000;
+
+/// Default time given to each endpoint to connect, retries included, in milliseconds.
+pub const DEFAULT_CONNECT_DEADLINE: u64 = 10_000;
+
+/// Key identifying the same message delivered by different endpoints.
+pub trait BloxrouteDedupKey {
+    fn dedup_key(&self) -> B256;
+}
+
+impl BloxrouteDedupKey for BloxrouteTransaction {
+    fn dedup_key(&self) -> B256 {
+        self.txHash
+    }
+}
+
+impl BloxrouteDedupKey for BloxrouteBlock {
+    fn dedup_key(&self) -> B256 {
+        self.hash
+    }
+}
+
+/// A message of a merged stream, along with the endpoint that delivered it first and how far
+/// ahead of the next endpoint it was.
+#[derive(Debug, Clone)]
+pub struct BloxrouteFanInItem<T> {
+    pub item: T,
+    /// Index of the winning endpoint in `BloxrouteMultiWsClient::clients`.
+    pub endpoint_index: usize,
+    pub endpoint: String,
+    lead: Arc<OnceLock<Duration>>,
+}
+
+impl<T> BloxrouteFanInItem<T> {
+    /// How far ahead of the next endpoint the winner delivered the message. The item is yielded
+    /// as soon as it first arrives, so this is `None` until a second endpoint delivers its copy,
+    /// and stays `None` if none does.
+    pub fn lead(&self) -> Option<Duration> {
+        self.lead.get().copied()
+    }
+}
+
+/// How an endpoint fared against the others on a merged stream.
+#[derive(Debug, Clone, Default)]
+pub struct BloxrouteEndpointStats {
+    pub endpoint: String,
+    /// Messages this endpoint delivered first.
+    pub wins: u64,
+    /// Messages another endpoint delivered first.
+    pub losses: u64,
+    /// Later copies, delivered by other endpoints, of the messages this endpoint won.
+    pub outpaced: u64,
+    /// Sum of how far ahead of each of those copies this endpoint was.
+    pub total_lead: Duration,
+}
+
+impl BloxrouteEndpointStats {
+    /// Average lead over the later copies of the messages this endpoint won.
+    pub fn average_lead(&self) -> Option<Duration> {
+        let copies = u32::try_from(self.outpaced).unwrap_or(u32::MAX);
+        (copies > 0).then(|| self.total_lead / copies)
+    }
+}
+
+/// First arrivals of the most recent keys, evicted oldest first.
+#[derive(Debug)]
+struct BloxrouteDedup {
+    capacity: usize,
+    seen: HashMap<B256, (usize, Instant, Arc<OnceLock<Duration>>)>,
+    order: VecDeque<B256>,
+    stats: Vec<BloxrouteEndpointStats>,
+}
+
+impl BloxrouteDedup {
+    /// Records an arrival, returns the lead to fill in if it is the first one.
+    fn arrive(
+        &mut self,
+        key: B256,
+        endpoint_index: usize,
+        at: Instant,
+    ) -> Option<Arc<OnceLock<Duration>>> {
+        if let Some((winner, first, lead)) = self.seen.get(&key) {
+            let elapsed = at.saturating_duration_since(*first);
+            lead.get_or_init(|| elapsed);
+            let winner = &mut self.stats[*winner];
+            winner.outpaced += 1;
+            winner.total_lead += elapsed;
+            self.stats[endpoint_index].losses += 1;
+            return None;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        let lead = Arc::new(OnceLock::new());
+        self.seen.insert(key, (endpoint_index, at, lead.clone()));
+        self.order.push_back(key);
+        self.stats[endpoint_index].wins += 1;
+        Some(lead)
+    }
+}
+
+/// Holds one connection per endpoint and merges the same feed from all of them.
+#[derive(Clone, Debug)]
+pub struct BloxrouteMultiWsClient {
+    pub clients: Vec<BloxrouteWsClient>,
+    /// Number of recent tx/block hashes remembered to drop duplicates.
+    pub dedup_capacity: usize,
+}
+
+impl BloxrouteMultiWsClient {
+    /// Connects to every endpoint concurrently. Endpoints that cannot be reached within
+    /// `DEFAULT_CONNECT_DEADLINE` are left out, fails only if none of them can.
+    pub async fn connect(
+        configs: Vec<BloxrouteClientBuilder>,
+    ) -> Result<BloxrouteMultiWsClient, BloxrouteError> {
+        Self::connect_with_deadline(configs, Duration::from_millis(DEFAULT_CONNECT_DEADLINE)).await
+    }
+
+    /// Same as `connect`, giving each endpoint `deadline` to connect. Reconnects after that
+    /// follow the endpoint's reconnect policy.
+    pub async fn connect_with_deadline(
+        configs: Vec<BloxrouteClientBuilder>,
+        deadline: Duration,
+    ) -> Result<BloxrouteMultiWsClient, BloxrouteError> {
+        let connections = configs.into_iter().map(|config| async move {
+            time::timeout(deadline, config.connect_ws())
+                .await
+                .unwrap_or(Err(BloxrouteError::Timeout))
+        });
+        let mut clients = vec![];
+        let mut last_err = BloxrouteError::ConnectionClosed;
+        for result in join_all(connections).await {
+            match result {
+                Ok(client) => clients.push(client),
+                Err(err) => {
+                    println!("Error connecting to websocket {}", err);
+                    last_err = err;
+                }
+            }
+        }
+        if clients.is_empty() {
+            return Err(last_err);
+        }
+        Ok(Self {
+            clients,
+            dedup_capacity: DEFAULT_DEDUP_CAPACITY,
+        })
+    }
+
+    pub async fn subscribe_to_new_txs(
+        &mut self,
+        id: String,
+        params: BloxrouteTransactionRequestInclude,
+    ) -> Result<BloxrouteMultiStream<BloxrouteTransaction>, BloxrouteError> {
+        let subscriptions = self
+            .clients
+            .iter_mut()
+            .map(|client| client.subscribe_to_new_txs(id.clone(), params.clone()));
+        let streams = join_all(subscriptions).await;
+        self.fan_in(streams)
+    }
+
+    pub async fn subscribe_to_pending_txs(
+        &self,
+        id: String,
+        params: BloxrouteTransactionRequestInclude,
+    ) -> Result<BloxrouteMultiStream<BloxrouteTransaction>, BloxrouteError> {
+        let subscriptions = self
+            .clients
+            .iter()
+            .map(|client| client.subscribe_to_pending_txs(id.clone(), params.clone()));
+        let streams = join_all(subscriptions).await;
+        self.fan_in(streams)
+    }
+
+    pub async fn subscribe_to_bdn_blocks(
+        &self,
+        id: String,
+        params: BloxrouteBlockRequestInclude,
+    ) -> Result<BloxrouteMultiStream<BloxrouteBlock>, BloxrouteError> {
+        let subscriptions = self
+            .clients
+            .iter()
+            .map(|client| client.subscribe_to_bdn_blocks(id.clone(), params.clone()));
+        let streams = join_all(subscriptions).await;
+        self.fan_in(streams)
+    }
+
+    /// Merges one stream per client, keeping the first copy of every message. Endpoints that
+    /// rejected the subscription are left out, fails only if all of them did.
+    fn fan_in<T>(
+        &self,
+        streams: Vec<Result<BloxrouteStream<T>, BloxrouteError>>,
+    ) -> Result<BloxrouteMultiStream<T>, BloxrouteError>
+    where
+        T: BloxrouteStreamItem + BloxrouteDedupKey + Send + 'static,
+    {
+        let mut opened = vec![];
+        let mut last_err = BloxrouteError::ConnectionClosed;
+        for (endpoint_index, stream) in streams.into_iter().enumerate() {
+            match stream {
+                Ok(stream) => opened.push((endpoint_index, stream)),
+                Err(err) => {
+                    println!(
+                        "Error subscribing on {}: {}",
+                        self.clients[endpoint_index].config.endpoint, err
+                    );
+                    last_err = err;
+                }
+            }
+        }
+        if opened.is_empty() {
+            return Err(last_err);
+        }
+        let endpoints: Vec<String> = self
+            .clients
+            .iter()
+            .map(|client| client.config.endpoint.clone())
+            .collect();
+        let dedup = Arc::new(Mutex::new(BloxrouteDedup {
+            capacity: self.dedup_capacity.max(1),
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            stats: endpoints
+                .iter()
+                .map(|endpoint| BloxrouteEndpointStats {
+                    endpoint: endpoint.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+        }));
+
+        let capacity = self.clients[0].config.channel_capacity;
+        let (tx, rx) = mpsc::channel(capacity);
+        let tasks = opened
+            .into_iter()
+            .map(|(endpoint_index, mut stream)| {
+                let tx = tx.clone();
+                let dedup = dedup.clone();
+                let endpoint = endpoints[endpoint_index].clone();
+                tokio::spawn(async move {
+                    while let Some(item) = stream.next().await {
+                        let lead = dedup.lock().unwrap().arrive(
+                            item.dedup_key(),
+                            endpoint_index,
+                            Instant::now(),
+                        );
+                        let Some(lead) = lead else {
+                            continue;
+                        };
+                        let item = BloxrouteFanInItem {
+                            item,
+                            endpoint_index,
+                            endpoint: endpoint.clone(),
+                            lead,
+                        };
+                        if tx.send(item).await.is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Ok(BloxrouteMultiStream { rx, tasks, dedup })
+    }
+}
+
+/// Deduplicated stream merged from several endpoints. Dropping it unsubscribes from all of them.
+#[derive(Debug)]
+pub struct BloxrouteMultiStream<T> {
+    rx: Receiver<BloxrouteFanInItem<T>>,
+    tasks: Vec<JoinHandle<()>>,
+    dedup: Arc<Mutex<BloxrouteDedup>>,
+}
+
+impl<T> BloxrouteMultiStream<T> {
+    /// Per-endpoint wins and leads so far, in the order of `BloxrouteMultiWsClient::clients`.
+    pub fn stats(&self) -> Vec<BloxrouteEndpointStats> {
+        self.dedup.lock().unwrap().stats.clone()
+    }
+}
+
+impl<T> Drop for BloxrouteMultiStream<T> {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl<T> Stream for BloxrouteMultiStream<T> {
+    type Item = BloxrouteFanInItem<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
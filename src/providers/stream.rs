@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures_util::{stream, Stream, StreamExt};
+use tokio::sync::mpsc::{Receiver, UnboundedSender};
+
+use crate::models::{
+    block::BloxrouteBlock,
+    eth_on_block::{BloxrouteEthOnBlockResponse, BloxrouteEthOnBlockResults},
+    openbook::{
+        BloxrouteOpenbookGetDepthResponse, BloxrouteOpenbookGetMarketsResponse,
+        BloxrouteOpenbookGetOpenOrdersResponse, BloxrouteOpenbookGetOrderbookResponse,
+        BloxrouteOpenbookGetTickersResponse, BloxrouteOpenbookGetUnsettledResponse,
+        BloxrouteOpenbookOrderbookStreamResponse, BloxrouteOpenbookTickerStreamResponse,
+    },
+    raydium::{
+        BloxrouteRaydiumNewRaydiumPoolsResponse, BloxrouteRaydiumStreamReservesResponse,
+        BloxrouteRaydiumStreamSwapsResponse,
+    },
+    receipt::BloxrouteTxReceipt,
+    solana::{BloxrouteGetBundleTipStreamResponse, BloxrouteGetStreamPriorityFee},
+    transaction::{BloxrouteTransaction, BloxrouteTransactionStatus},
+    BloxrouteEnvelope, BloxrouteResponseEnum, FromEnvelope,
+};
+
+/// A message type a stream can carry: how to decode it from a frame and pull it back out of the
+/// `BloxrouteResponseEnum` it is routed as.
+pub trait BloxrouteStreamItem: Sized {
+    fn decode(envelope: &BloxrouteEnvelope) -> Result<BloxrouteResponseEnum, serde_json::Error>;
+    fn from_response(response: BloxrouteResponseEnum) -> Option<Self>;
+}
+
+macro_rules! stream_item {
+    ($item:ty, $variant:ident, $res:ident => $result:expr) => {
+        impl BloxrouteStreamItem for $item {
+            fn decode(
+                envelope: &BloxrouteEnvelope,
+            ) -> Result<BloxrouteResponseEnum, serde_json::Error> {
+                Ok(BloxrouteResponseEnum::$variant(
+                    FromEnvelope::from_envelope(envelope)?,
+                ))
+            }
+
+            fn from_response(response: BloxrouteResponseEnum) -> Option<Self> {
+                match response {
+                    BloxrouteResponseEnum::$variant($res) => Some($result),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+stream_item!(BloxrouteTransaction, Transaction, res => res.params.result);
+stream_item!(BloxrouteBlock, Block, res => res.params.result);
+stream_item!(BloxrouteTransactionStatus, TransactionStatus, res => res.params.result);
+stream_item!(BloxrouteTxReceipt, TxReceipt, res => res.params.result);
+stream_item!(BloxrouteEthOnBlockResponse, EthOnBlock, res => res.params.result);
+stream_item!(BloxrouteGetStreamPriorityFee, GetStreamPriorityFee, res => res.params.result);
+stream_item!(BloxrouteGetBundleTipStreamResponse, GetBundleTipStream, res => res.params.result);
+stream_item!(BloxrouteRaydiumStreamReservesResponse, RaydiumStreamReservesResponse, res => res.params.result);
+stream_item!(BloxrouteRaydiumStreamSwapsResponse, RaydiumStreamSwapsResponse, res => res.params.result);
+stream_item!(BloxrouteRaydiumNewRaydiumPoolsResponse, RaydiumNewRaydiumPoolsResponse, res => res.params.result);
+stream_item!(BloxrouteOpenbookGetMarketsResponse, OpenbookGetMarkets, res => res.result);
+stream_item!(BloxrouteOpenbookGetOrderbookResponse, OpenbookGetOrderbookResponse, res => res.result);
+stream_item!(BloxrouteOpenbookGetDepthResponse, OpenbookGetDepth, res => res.result);
+stream_item!(BloxrouteOpenbookGetTickersResponse, OpenbookGetTickers, res => res.result);
+stream_item!(BloxrouteOpenbookGetOpenOrdersResponse, OpenbookGetOpenOrders, res => res.result);
+stream_item!(BloxrouteOpenbookGetUnsettledResponse, OpenbookGetUnsettled, res => res.result);
+stream_item!(BloxrouteOpenbookOrderbookStreamResponse, OpenbookOrderbooksStream, res => res.params.result);
+stream_item!(BloxrouteOpenbookTickerStreamResponse, OpenbookTickersStream, res => res.params.result);
+
+/// Typed stream of the messages routed to a single request.
+///
+/// Subscriptions keep yielding until they are unsubscribed, one-shot
+/// requests (e.g. Openbook `GetMarkets`) yield their response and then end.
+/// Dropping the stream unsubscribes it.
+///
+/// Messages are buffered up to the client's channel capacity. Once the buffer is full, new
+/// messages are dropped rather than holding up the other streams, and counted in `lagged`.
+#[derive(Debug)]
+pub struct BloxrouteStream<T> {
+    pub id: String,
+    /// Id the gateway assigned to the subscription, `None` for one-shot requests.
+    /// The gateway assigns a new one when the subscription is replayed after a reconnect.
+    pub subscription_id: Option<String>,
+    rx: Receiver<BloxrouteResponseEnum>,
+    dropped: Option<UnboundedSender<String>>,
+    lagged: Arc<AtomicU64>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T> BloxrouteStream<T> {
+    pub fn new(
+        id: String,
+        rx: Receiver<BloxrouteResponseEnum>,
+        dropped: Option<UnboundedSender<String>>,
+    ) -> BloxrouteStream<T> {
+        Self {
+            id,
+            subscription_id: None,
+            rx,
+            dropped,
+            lagged: Arc::new(AtomicU64::new(0)),
+            item: PhantomData,
+        }
+    }
+
+    /// Number of messages dropped so far because the stream was not polled fast enough.
+    pub fn lagged(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn lag_counter(&self) -> Arc<AtomicU64> {
+        self.lagged.clone()
+    }
+}
+
+impl BloxrouteStream<BloxrouteEthOnBlockResponse> {
+    /// Groups the responses by block, yielding them once the block's `TaskCompletedEvent`
+    /// arrives. Blocks left incomplete, e.g. because responses were dropped, are skipped.
+    pub fn by_block(self) -> impl Stream<Item = BloxrouteEthOnBlockResults> {
+        stream::unfold(
+            (self, BTreeMap::<u64, BloxrouteEthOnBlockResults>::new()),
+            |(mut responses, mut blocks)| async move {
+                while let Some(response) = responses.next().await {
+                    let height = response.blockHeight;
+                    if response.is_task_completed() {
+                        let results =
+                            blocks
+                                .remove(&height)
+                                .unwrap_or_else(|| BloxrouteEthOnBlockResults {
+                                    blockHeight: height,
+                                    ..Default::default()
+                                });
+                        blocks.retain(|pending, _| *pending > height);
+                        return Some((results, (responses, blocks)));
+                    }
+                    blocks
+                        .entry(height)
+                        .or_insert_with(|| BloxrouteEthOnBlockResults {
+                            blockHeight: height,
+                            ..Default::default()
+                        })
+                        .responses
+                        .insert(response.name.clone(), response);
+                }
+                None
+            },
+        )
+    }
+}
+
+impl<T> Drop for BloxrouteStream<T> {
+    fn drop(&mut self) {
+        // Close the receiver first so the client can tell this stream apart from a newer one with the same id.
+        self.rx.close();
+        if let Some(dropped) = &self.dropped {
+            let _ = dropped.send(self.id.clone());
+        }
+    }
+}
+
+impl<T: BloxrouteStreamItem> Stream for BloxrouteStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            match self.rx.poll_recv(cx) {
+                Poll::Ready(Some(response)) => {
+                    if let Some(item) = T::from_response(response) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -1,87 +1,514 @@
-use serde::Serialize;
-use std::{collections::HashMap, error::Error, sync::Arc, thread, time::Duration};
-use tokio::sync::mpsc::{Receiver, Sender};
+use alloy_primitives::B256;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Serialize,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{error::TrySendError, Sender, UnboundedReceiver, UnboundedSender},
+    oneshot, watch,
+};
 
-use async_recursion::async_recursion;
 use futures_util::{
     lock::Mutex,
     stream::{SplitSink, SplitStream},
     SinkExt, StreamExt,
 };
-use serde_json::json;
-use tokio::{net::TcpStream, sync::mpsc};
+use serde_json::{json, value::RawValue, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::mpsc,
+    time::{self, sleep},
+};
 use tokio_tungstenite::{
-    connect_async,
-    tungstenite::{client::IntoClientRequest, protocol::Message},
-    MaybeTlsStream, WebSocketStream,
+    client_async_tls_with_config, connect_async_tls_with_config,
+    tungstenite::{
+        self,
+        client::IntoClientRequest,
+        http::{header::USER_AGENT, HeaderValue, Uri},
+        protocol::Message,
+    },
+    Connector, MaybeTlsStream, WebSocketStream,
 };
 
-use crate::models::{
-    block::BloxrouteBlock,
-    openbook::{
-        BloxrouteOpenbookGetDepthResponse, BloxrouteOpenbookGetMarketsResponse,
-        BloxrouteOpenbookGetOrderbookResponse, BloxrouteOpenbookGetTickersResponse,
-    },
-    raydium::{
-        BloxrouteRaydiumNewRaydiumPoolsResponse, BloxrouteRaydiumStreamReservesResponse,
-        BloxrouteRaydiumStreamSwapsPayload, BloxrouteRaydiumStreamSwapsResponse,
+use crate::{
+    error::{BloxrouteError, BloxrouteJsonRpcError},
+    models::{
+        block::BloxrouteBlock,
+        bundle::{
+            BloxrouteBundle, BloxrouteBundleSimulation, BloxrouteSimulateBundleRequest,
+            BloxrouteSubmitBundleResponse,
+        },
+        eth_on_block::{BloxrouteEthOnBlockRequest, BloxrouteEthOnBlockResponse},
+        openbook::{
+            BloxrouteOpenbookGetDepthResponse, BloxrouteOpenbookGetMarketsResponse,
+            BloxrouteOpenbookGetOpenOrdersResponse, BloxrouteOpenbookGetOrderbookResponse,
+            BloxrouteOpenbookGetTickersResponse, BloxrouteOpenbookGetUnsettledResponse,
+            BloxrouteOpenbookOrderbookStreamResponse, BloxrouteOpenbookOrderbooksStreamPayload,
+            BloxrouteOpenbookTickerStreamResponse,
+        },
+        raydium::{
+            BloxrouteRaydiumNewRaydiumPoolsResponse, BloxrouteRaydiumStreamReservesResponse,
+            BloxrouteRaydiumStreamSwapsPayload, BloxrouteRaydiumStreamSwapsResponse,
+        },
+        receipt::BloxrouteTxReceipt,
+        solana::{BloxrouteGetBundleTipStreamResponse, BloxrouteGetStreamPriorityFee},
+        transaction::{
+            BloxrouteBlockRequestInclude, BloxrouteCancelPrivateTransactionRequest,
+            BloxrouteSendBatchTransactionsRequest, BloxrouteSendBatchTransactionsResponse,
+            BloxrouteSendPrivateTransactionRequest, BloxrouteSendTransactionRequest,
+            BloxrouteSendTransactionResponse, BloxrouteTransaction,
+            BloxrouteTransactionRequestInclude, BloxrouteTransactionStatus,
+            BloxrouteTransactionStatusRequest,
+        },
+        BloxrouteEnvelope, BloxrouteRequestParams, BloxrouteRequestPayload, BloxrouteResponseEnum,
     },
-    solana::{BloxrouteGetBundleTipStreamResponse, BloxrouteGetStreamPriorityFee},
-    subscription::BloxrouteSubscription,
-    transaction::{
-        BloxrouteBlockRequestInclude, BloxrouteTransaction, BloxrouteTransactionRequestInclude,
+    providers::{
+        builder::{BloxrouteClientBuilder, BloxrouteReconnectPolicy, BloxrouteTlsOptions},
+        stream::{BloxrouteStream, BloxrouteStreamItem},
     },
-    BloxrouteGeneric, BloxrouteGenericSolana, BloxrouteRequestParams, BloxrouteRequestPayload,
-    BloxrouteResponseEnum,
 };
 
+/// Upper bound for the size of a proxy's answer to `CONNECT`.
+const MAX_PROXY_RESPONSE: usize = 8192;
+
+type WsWrite = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type WsRead = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+
+/// Resolves with the gateway's response to a request, or the error it answered with.
+type BloxrouteResponder = oneshot::Sender<Result<Value, BloxrouteError>>;
+
+/// Where the frames of one request go and how to decode them.
+#[derive(Debug)]
+pub(crate) struct BloxrouteRoute {
+    pub(crate) sender: Sender<BloxrouteResponseEnum>,
+    /// Messages dropped because the stream's channel was full.
+    pub(crate) lagged: Arc<AtomicU64>,
+    pub(crate) decode: fn(&BloxrouteEnvelope) -> Result<BloxrouteResponseEnum, serde_json::Error>,
+    /// One-shot routes (e.g. Openbook `GetMarkets`) are answered by a single response keyed by
+    /// request id, subscriptions by notifications keyed by subscription id.
+    pub(crate) one_shot: bool,
+}
+
+/// Routing state shared between the client and its reader task.
+#[derive(Debug, Default)]
+pub(crate) struct BloxrouteRoutes {
+    /// Request id -> route of the stream opened by that request.
+    pub(crate) streams: HashMap<String, BloxrouteRoute>,
+    /// Server-assigned subscription id -> request id.
+    pub(crate) subscriptions: HashMap<String, String>,
+    /// Request id -> `subscribe` payload, replayed after a reconnect.
+    pub(crate) payloads: HashMap<String, String>,
+    /// Request id -> waiter for the server's response to a request (e.g. `subscribe`, `unsubscribe`).
+    pub(crate) pending: HashMap<String, BloxrouteResponder>,
+}
+
+impl BloxrouteRoutes {
+    pub(crate) fn remove(&mut self, request_id: &str) {
+        self.streams.remove(request_id);
+        self.payloads.remove(request_id);
+        self.pending.remove(request_id);
+        self.subscriptions.retain(|_, id| id != request_id);
+    }
+
+    /// Current server-assigned subscription id of a request id.
+    pub(crate) fn subscription_id(&self, request_id: &str) -> Option<String> {
+        self.subscriptions
+            .iter()
+            .find(|(_, id)| *id == request_id)
+            .map(|(subscription_id, _)| subscription_id.clone())
+    }
+
+    /// Removes the route of a request id, returning its subscription id if it was acknowledged.
+    pub(crate) fn take(&mut self, request_id: &str) -> Option<String> {
+        let subscription_id = self.subscription_id(request_id);
+        self.remove(request_id);
+        subscription_id
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BloxrouteWsClient {
-    pub endpoint: String,
-    pub auth_header: String,
-    pub write: Arc<Mutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>,
-    pub read: Arc<Mutex<SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>>>,
-    pub tx: Arc<Mutex<Sender<BloxrouteResponseEnum>>>,
-    pub rx: Arc<Mutex<Receiver<BloxrouteResponseEnum>>>,
+    pub config: BloxrouteClientBuilder,
+    pub write: Arc<Mutex<WsWrite>>,
+    pub read: Arc<Mutex<WsRead>>,
+    pub(crate) routes: Arc<Mutex<BloxrouteRoutes>>,
+    pub request_counter: Arc<AtomicU64>,
+    /// Notified with the request id of every dropped stream so it can be unsubscribed.
+    pub dropped: UnboundedSender<String>,
+    /// Set by `close`. Dropped along with the last clone of the client, which also stops the
+    /// background tasks.
+    pub shutdown: Arc<watch::Sender<bool>>,
+}
+
+/// Handle the background tasks keep on the client, so they don't keep it alive.
+#[derive(Clone, Debug)]
+struct BloxrouteWsClientWeak {
+    config: BloxrouteClientBuilder,
+    write: Weak<Mutex<WsWrite>>,
+    read: Weak<Mutex<WsRead>>,
+    routes: Weak<Mutex<BloxrouteRoutes>>,
+    request_counter: Weak<AtomicU64>,
+    dropped: UnboundedSender<String>,
+    shutdown: Weak<watch::Sender<bool>>,
+}
+
+impl BloxrouteWsClientWeak {
+    fn upgrade(&self) -> Option<BloxrouteWsClient> {
+        Some(BloxrouteWsClient {
+            config: self.config.clone(),
+            write: self.write.upgrade()?,
+            read: self.read.upgrade()?,
+            routes: self.routes.upgrade()?,
+            request_counter: self.request_counter.upgrade()?,
+            dropped: self.dropped.clone(),
+            shutdown: self.shutdown.upgrade()?,
+        })
+    }
 }
 
 impl BloxrouteWsClient {
-    #[async_recursion]
-    pub async fn connect(endpoint: String, auth_header: String, timeout: u64) -> BloxrouteWsClient {
-        let mut req = endpoint.clone().into_client_request().unwrap();
-        let headers = req.headers_mut();
-        headers.append("Authorization", auth_header.parse().unwrap());
-
-        let (tx, rx) = mpsc::channel::<BloxrouteResponseEnum>(1000);
-
-        match connect_async(req).await {
-            Ok((stream, _)) => {
-                let (write, read) = stream.split();
-                println!("Successfully connected to bloxroute");
-                let client = Self {
-                    auth_header,
-                    endpoint,
-                    write: Arc::new(Mutex::new(write)),
-                    read: Arc::new(Mutex::new(read)),
-                    tx: Arc::new(Mutex::new(tx)),
-                    rx: Arc::new(Mutex::new(rx)),
-                };
-                BloxrouteWsClient::init(&client).await;
-                return client;
+    /// Connects with the default configuration, retrying after `timeout` milliseconds (doubled
+    /// on every attempt) while the failure is retryable (e.g. the endpoint is unreachable).
+    pub async fn connect(
+        endpoint: String,
+        auth_header: String,
+        timeout: u64,
+    ) -> Result<BloxrouteWsClient, BloxrouteError> {
+        let policy = BloxrouteReconnectPolicy {
+            initial_backoff: Duration::from_millis(timeout.max(1)),
+            ..Default::default()
+        };
+        BloxrouteClientBuilder::new(endpoint, auth_header)
+            .reconnect_policy(policy)
+            .connect_ws()
+            .await
+    }
+
+    /// Connects to the gateway, retrying as the config's reconnect policy allows.
+    pub async fn connect_with(
+        config: BloxrouteClientBuilder,
+    ) -> Result<BloxrouteWsClient, BloxrouteError> {
+        let (dropped, dropped_rx) = mpsc::unbounded_channel::<String>();
+        let (write, read) = open_socket_with_retry(&config).await?;
+        println!("Successfully connected to bloxroute");
+        let client = Self {
+            config,
+            write: Arc::new(Mutex::new(write)),
+            read: Arc::new(Mutex::new(read)),
+            routes: Arc::new(Mutex::new(BloxrouteRoutes::default())),
+            request_counter: Arc::new(AtomicU64::new(0)),
+            dropped,
+            shutdown: Arc::new(watch::channel(false).0),
+        };
+        BloxrouteWsClient::init(&client).await;
+        tokio::spawn(watch_dropped_streams(
+            client.downgrade(),
+            dropped_rx,
+            client.shutdown.subscribe(),
+        ));
+        Ok(client)
+    }
+
+    fn downgrade(&self) -> BloxrouteWsClientWeak {
+        BloxrouteWsClientWeak {
+            config: self.config.clone(),
+            write: Arc::downgrade(&self.write),
+            read: Arc::downgrade(&self.read),
+            routes: Arc::downgrade(&self.routes),
+            request_counter: Arc::downgrade(&self.request_counter),
+            dropped: self.dropped.clone(),
+            shutdown: Arc::downgrade(&self.shutdown),
+        }
+    }
+
+    /// Closes the socket and stops reconnecting. Every stream ends and every request still
+    /// waiting for a response fails.
+    ///
+    /// Dropping the last clone of the client has the same effect.
+    pub async fn close(&self) -> Result<(), BloxrouteError> {
+        self.shutdown.send_replace(true);
+        let mut write = self.write.lock().await;
+        *self.routes.lock().await = BloxrouteRoutes::default();
+        write.close().await?;
+        Ok(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Registers a stream for the request id. Reusing an id replaces (and ends) the previous
+    /// stream, whose subscription is cancelled.
+    async fn open_stream<T: BloxrouteStreamItem>(
+        &self,
+        id: &str,
+        one_shot: bool,
+    ) -> BloxrouteStream<T> {
+        let (tx, rx) = mpsc::channel::<BloxrouteResponseEnum>(self.config.channel_capacity);
+        let stream = BloxrouteStream::new(id.to_string(), rx, Some(self.dropped.clone()));
+        let mut routes = self.routes.lock().await;
+        if let Some(subscription_id) = routes.take(id) {
+            let client = self.clone();
+            tokio::spawn(async move {
+                if let Err(err) = client.cancel_subscription(subscription_id.clone()).await {
+                    println!("Error unsubscribing from {}: {}", subscription_id, err);
+                }
+            });
+        }
+        routes.streams.insert(
+            id.to_string(),
+            BloxrouteRoute {
+                sender: tx,
+                lagged: stream.lag_counter(),
+                decode: T::decode,
+                one_shot,
+            },
+        );
+        stream
+    }
+
+    fn next_request_id(&self, prefix: &str) -> String {
+        let id = self.request_counter.fetch_add(1, Ordering::Relaxed);
+        format!("{prefix}-{id}")
+    }
+
+    /// Returns the server-assigned subscription id for a request id once it has been acknowledged.
+    pub async fn get_subscription_id(&self, id: &str) -> Option<String> {
+        self.routes.lock().await.subscription_id(id)
+    }
+
+    /// Ends the stream opened by the request id (`stream.id`) and asks the gateway to cancel its
+    /// subscription, under the subscription id it currently has.
+    ///
+    /// Resolves with the server's acknowledgement, `false` if the subscription was not
+    /// acknowledged yet. A late acknowledgement is unsubscribed when it arrives.
+    pub async fn unsubscribe(&self, id: &str) -> Result<bool, BloxrouteError> {
+        let subscription_id = self.routes.lock().await.take(id);
+        match subscription_id {
+            Some(subscription_id) => self.cancel_subscription(subscription_id).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Asks the gateway to cancel a subscription by the id it assigned.
+    async fn cancel_subscription(&self, subscription_id: String) -> Result<bool, BloxrouteError> {
+        let params = BloxrouteRequestParams::Array(vec![subscription_id]);
+        let ack = self.send_control("unsubscribe", params).await?;
+        Ok(ack.as_bool().unwrap_or(false))
+    }
+
+    /// Sends a request that opens no stream and resolves with the gateway's response.
+    async fn send_control<P: Serialize>(
+        &self,
+        method: &str,
+        params: BloxrouteRequestParams<P>,
+    ) -> Result<Value, BloxrouteError> {
+        let req_payload = BloxrouteRequestPayload {
+            id: self.next_request_id(method),
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        };
+        self.send_and_confirm(req_payload, false).await
+    }
+
+    /// Sends a JSON-RPC request and decodes its result.
+    async fn request<T: DeserializeOwned, P: Serialize>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<T, BloxrouteError> {
+        let result = self
+            .send_control(method, BloxrouteRequestParams::Object(params))
+            .await?;
+        let body = result.to_string();
+        serde_json::from_value(result).map_err(|err| BloxrouteError::deserialization(err, &body))
+    }
+
+    /// Sends a signed transaction through the BDN (`blxr_tx`) and returns its hash.
+    pub async fn send_transaction(
+        &self,
+        params: BloxrouteSendTransactionRequest,
+    ) -> Result<B256, BloxrouteError> {
+        let response: BloxrouteSendTransactionResponse = self.request("blxr_tx", params).await?;
+        Ok(response.txHash)
+    }
+
+    /// Sends several signed transactions through the BDN (`blxr_batch_tx`) and returns their hashes.
+    pub async fn send_batch_transactions(
+        &self,
+        params: BloxrouteSendBatchTransactionsRequest,
+    ) -> Result<Vec<B256>, BloxrouteError> {
+        let response: BloxrouteSendBatchTransactionsResponse =
+            self.request("blxr_batch_tx", params).await?;
+        Ok(response.txHashes)
+    }
+
+    /// Sends a transaction to MEV builders only (`blxr_private_tx`) and returns its hash.
+    pub async fn send_private_transaction(
+        &self,
+        params: BloxrouteSendPrivateTransactionRequest,
+    ) -> Result<B256, BloxrouteError> {
+        let response: BloxrouteSendTransactionResponse =
+            self.request("blxr_private_tx", params).await?;
+        Ok(response.txHash)
+    }
+
+    /// Withdraws a private transaction from the builders (`blxr_private_tx_cancel`).
+    pub async fn cancel_private_transaction(
+        &self,
+        params: BloxrouteCancelPrivateTransactionRequest,
+    ) -> Result<(), BloxrouteError> {
+        let _: IgnoredAny = self.request("blxr_private_tx_cancel", params).await?;
+        Ok(())
+    }
+
+    /// Submits a bundle to MEV builders (`blxr_submit_bundle`) and returns its hash.
+    pub async fn submit_bundle(&self, bundle: BloxrouteBundle) -> Result<String, BloxrouteError> {
+        let response: BloxrouteSubmitBundleResponse =
+            self.request("blxr_submit_bundle", bundle).await?;
+        Ok(response.bundleHash)
+    }
+
+    /// Simulates a bundle (`blxr_simulate_bundle`) without submitting it.
+    pub async fn simulate_bundle(
+        &self,
+        params: BloxrouteSimulateBundleRequest,
+    ) -> Result<BloxrouteBundleSimulation, BloxrouteError> {
+        self.request("blxr_simulate_bundle", params).await
+    }
+
+    /// Applies `update` to the `subscribe` payload replayed after a reconnect.
+    async fn update_payload(&self, request_id: &str, update: impl FnOnce(&mut Value)) {
+        let mut routes = self.routes.lock().await;
+        if let Some(data) = routes.payloads.get_mut(request_id) {
+            if let Ok(mut payload) = serde_json::from_str::<Value>(data) {
+                update(&mut payload);
+                *data = payload.to_string();
             }
-            Err(err) => {
-                println!("Error connecting to websocket {}", err);
-                thread::sleep(Duration::from_millis(timeout));
-                BloxrouteWsClient::connect(endpoint, auth_header, timeout).await
+        }
+    }
+
+    /// Unsubscribes the route of a stream whose handle has been dropped.
+    async fn unsubscribe_dropped(&self, request_id: String) {
+        let subscription_id = {
+            let mut routes = self.routes.lock().await;
+            match routes.streams.get(&request_id) {
+                // A newer stream reusing the same request id is still alive.
+                Some(route) if !route.sender.is_closed() => return,
+                Some(_) => {}
+                None => return,
             }
+            routes.take(&request_id)
+        };
+
+        if let Some(subscription_id) = subscription_id {
+            let client = self.clone();
+            tokio::spawn(async move {
+                if let Err(err) = client.cancel_subscription(subscription_id.clone()).await {
+                    println!("Error unsubscribing from {}: {}", subscription_id, err);
+                }
+            });
         }
     }
 
+    /// Sends a one-shot request whose response is delivered on the returned stream.
+    ///
+    /// Fails with the gateway's error if it rejects the request.
+    async fn send_request<T: BloxrouteStreamItem, P: Serialize>(
+        &self,
+        req_payload: BloxrouteRequestPayload<P>,
+    ) -> Result<BloxrouteStream<T>, BloxrouteError> {
+        let stream = self.open_stream(&req_payload.id, true).await;
+        self.send_and_confirm(req_payload, false).await?;
+        Ok(stream)
+    }
+
+    /// Sends a `subscribe` request and remembers it so it can be replayed after a reconnect.
+    ///
+    /// Resolves once the gateway acknowledges the subscription, with its id set on the stream.
+    /// Fails with the gateway's error if it rejects the subscription.
+    async fn send_subscription<T: BloxrouteStreamItem, P: Serialize>(
+        &self,
+        req_payload: BloxrouteRequestPayload<P>,
+    ) -> Result<BloxrouteStream<T>, BloxrouteError> {
+        let mut stream = self.open_stream(&req_payload.id, false).await;
+        let ack = self.send_and_confirm(req_payload, true).await?;
+        stream.subscription_id = ack.as_str().map(str::to_string);
+        Ok(stream)
+    }
+
+    /// Sends a request and waits up to the configured request timeout for the gateway's response.
+    /// `replay` requests are sent again after a reconnect.
+    async fn send_and_confirm<P: Serialize>(
+        &self,
+        req_payload: BloxrouteRequestPayload<P>,
+        replay: bool,
+    ) -> Result<Value, BloxrouteError> {
+        let result = async {
+            let response = self.send_registered(req_payload, replay).await?;
+            time::timeout(self.config.request_timeout, response)
+                .await
+                .map_err(|_| BloxrouteError::Timeout)?
+                .map_err(|_| BloxrouteError::ConnectionClosed)?
+        }
+        .await;
+        if result.is_err() {
+            // Drop the waiter left behind, the caller drops the stream which cleans up its route.
+            self.routes
+                .lock()
+                .await
+                .pending
+                .retain(|_, waiter| !waiter.is_closed());
+        }
+        result
+    }
+
+    /// Registers the waiter for the response (and the payload to replay) and sends the request,
+    /// all under the write lock. A reconnect thus sees either a request sent on the old socket
+    /// with its waiter, or neither.
+    async fn send_registered<P: Serialize>(
+        &self,
+        req_payload: BloxrouteRequestPayload<P>,
+        replay: bool,
+    ) -> Result<oneshot::Receiver<Result<Value, BloxrouteError>>, BloxrouteError> {
+        let id = req_payload.id.clone();
+        let data = json!(req_payload).to_string();
+        let (responder, response) = oneshot::channel();
+        let mut write = self.write.lock().await;
+        {
+            let mut routes = self.routes.lock().await;
+            routes.pending.insert(id.clone(), responder);
+            if replay {
+                routes.payloads.insert(id.clone(), data.clone());
+            }
+        }
+        if let Err(err) = write.send(Message::Text(data)).await {
+            let mut routes = self.routes.lock().await;
+            routes.pending.remove(&id);
+            routes.payloads.remove(&id);
+            return Err(err.into());
+        }
+        Ok(response)
+    }
+
     pub async fn subscribe_to_new_txs(
         &mut self,
         id: String,
         params: BloxrouteTransactionRequestInclude,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteTransaction>, BloxrouteError> {
+        if let Some(filter) = &params.filters {
+            filter.validate()?;
+        }
         let req_payload = BloxrouteRequestPayload {
             id,
             jsonrpc: "2.0".to_string(),
@@ -92,14 +519,17 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_pending_txs(
         &self,
         id: String,
         params: BloxrouteTransactionRequestInclude,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteTransaction>, BloxrouteError> {
+        if let Some(filter) = &params.filters {
+            filter.validate()?;
+        }
         let req_payload = BloxrouteRequestPayload {
             id,
             jsonrpc: "2.0".to_string(),
@@ -109,14 +539,87 @@ impl BloxrouteWsClient {
                 BloxrouteRequestParams::Object(params),
             ]),
         };
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
+    }
+
+    /// Follows the fate (pending, mined, dropped, replaced) of the given transactions.
+    pub async fn subscribe_to_transaction_status(
+        &self,
+        id: String,
+        params: BloxrouteTransactionStatusRequest,
+    ) -> Result<BloxrouteStream<BloxrouteTransactionStatus>, BloxrouteError> {
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "subscribe".to_string(),
+            params: BloxrouteRequestParams::Array(vec![
+                BloxrouteRequestParams::String("transactionStatus".to_string()),
+                BloxrouteRequestParams::Object(params),
+            ]),
+        };
+        self.send_subscription(req_payload).await
+    }
+
+    /// Adds transactions to follow on an existing `transactionStatus` subscription.
+    pub async fn watch_transactions(
+        &self,
+        stream: &BloxrouteStream<BloxrouteTransactionStatus>,
+        transactions: Vec<String>,
+    ) -> Result<bool, BloxrouteError> {
+        self.update_watched_transactions(stream, true, transactions)
+            .await
+    }
+
+    /// Stops following transactions on an existing `transactionStatus` subscription.
+    pub async fn unwatch_transactions(
+        &self,
+        stream: &BloxrouteStream<BloxrouteTransactionStatus>,
+        transactions: Vec<String>,
+    ) -> Result<bool, BloxrouteError> {
+        self.update_watched_transactions(stream, false, transactions)
+            .await
+    }
+
+    async fn update_watched_transactions(
+        &self,
+        stream: &BloxrouteStream<BloxrouteTransactionStatus>,
+        watch: bool,
+        transactions: Vec<String>,
+    ) -> Result<bool, BloxrouteError> {
+        let method = if watch {
+            "start_monitoring_tx"
+        } else {
+            "stop_monitoring_tx"
+        };
+        // Looked up rather than taken from the stream as it changes after a reconnect.
+        let subscription_id = self
+            .get_subscription_id(&stream.id)
+            .await
+            .ok_or(BloxrouteError::ConnectionClosed)?;
+        let params = BloxrouteRequestParams::Object(json!({
+            "subscription_id": subscription_id,
+            "transactions": transactions,
+        }));
+        let ack = self.send_control(method, params).await?;
+
+        // Keep the replayed subscription in sync with what the gateway now follows.
+        self.update_payload(&stream.id, |payload| {
+            if let Some(watched) = payload["params"][1]["transactions"].as_array_mut() {
+                watched.retain(|hash| !transactions.iter().any(|tx| hash == tx));
+                if watch {
+                    watched.extend(transactions.iter().map(|tx| json!(tx)));
+                }
+            }
+        })
+        .await;
+        Ok(ack.as_bool().unwrap_or(false))
     }
 
     pub async fn subscribe_to_new_blocks(
         &self,
         id: String,
         params: BloxrouteBlockRequestInclude,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteBlock>, BloxrouteError> {
         let req_payload = BloxrouteRequestPayload {
             id,
             jsonrpc: "2.0".to_string(),
@@ -126,14 +629,51 @@ impl BloxrouteWsClient {
                 BloxrouteRequestParams::Object(params),
             ]),
         };
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
+    }
+
+    /// Receipts of the transactions of every new block.
+    pub async fn subscribe_to_tx_receipts(
+        &self,
+        id: String,
+        params: BloxrouteBlockRequestInclude,
+    ) -> Result<BloxrouteStream<BloxrouteTxReceipt>, BloxrouteError> {
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "subscribe".to_string(),
+            params: BloxrouteRequestParams::Array(vec![
+                BloxrouteRequestParams::String("txReceipts".to_string()),
+                BloxrouteRequestParams::Object(params),
+            ]),
+        };
+        self.send_subscription(req_payload).await
+    }
+
+    /// Runs the request's calls at every new block. Each response carries the name of its call
+    /// and the block height, a `TaskCompletedEvent` response ends the responses of a block.
+    pub async fn subscribe_to_eth_on_block(
+        &self,
+        id: String,
+        params: BloxrouteEthOnBlockRequest,
+    ) -> Result<BloxrouteStream<BloxrouteEthOnBlockResponse>, BloxrouteError> {
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "subscribe".to_string(),
+            params: BloxrouteRequestParams::Array(vec![
+                BloxrouteRequestParams::String("ethOnBlock".to_string()),
+                BloxrouteRequestParams::Object(params),
+            ]),
+        };
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_bdn_blocks(
         &self,
         id: String,
         params: BloxrouteBlockRequestInclude,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteBlock>, BloxrouteError> {
         let req_payload = BloxrouteRequestPayload {
             id,
             jsonrpc: "2.0".to_string(),
@@ -143,7 +683,7 @@ impl BloxrouteWsClient {
                 BloxrouteRequestParams::Object(params),
             ]),
         };
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     // solana
@@ -151,7 +691,7 @@ impl BloxrouteWsClient {
     pub async fn subscribe_to_openbook_get_markets(
         &mut self,
         id: String,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetMarketsResponse>, BloxrouteError> {
         let hashmap: HashMap<String, String> = HashMap::new();
         let req_payload = BloxrouteRequestPayload {
             id,
@@ -160,7 +700,7 @@ impl BloxrouteWsClient {
             params: BloxrouteRequestParams::Hashmap(hashmap),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_request(req_payload).await
     }
 
     pub async fn subscribe_to_openbook_get_orderbooks(
@@ -168,7 +708,7 @@ impl BloxrouteWsClient {
         id: String,
         market: String,
         limit: Option<u64>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetOrderbookResponse>, BloxrouteError> {
         let mut hashmap: HashMap<String, String> = HashMap::new();
         hashmap.insert("market".to_string(), market);
         if let Some(limit) = limit {
@@ -181,7 +721,7 @@ impl BloxrouteWsClient {
             params: BloxrouteRequestParams::Hashmap(hashmap),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_request(req_payload).await
     }
 
     pub async fn subscribe_to_openbook_get_depth(
@@ -189,7 +729,7 @@ impl BloxrouteWsClient {
         id: String,
         market: String,
         limit: Option<u64>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetDepthResponse>, BloxrouteError> {
         let mut hashmap: HashMap<String, String> = HashMap::new();
         hashmap.insert("market".to_string(), market);
         if let Some(limit) = limit {
@@ -202,14 +742,14 @@ impl BloxrouteWsClient {
             params: BloxrouteRequestParams::Hashmap(hashmap),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_request(req_payload).await
     }
 
     pub async fn subscribe_to_openbook_get_tickers(
         &mut self,
         id: String,
         market: Vec<String>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetTickersResponse>, BloxrouteError> {
         let mut hashmap: HashMap<String, String> = HashMap::new();
         hashmap.insert("market".to_string(), market.join(","));
         let req_payload = BloxrouteRequestPayload {
@@ -219,7 +759,90 @@ impl BloxrouteWsClient {
             params: BloxrouteRequestParams::Hashmap(hashmap),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_request(req_payload).await
+    }
+
+    pub async fn subscribe_to_openbook_get_open_orders(
+        &mut self,
+        id: String,
+        market: String,
+        owner: String,
+        limit: Option<u64>,
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetOpenOrdersResponse>, BloxrouteError> {
+        let mut hashmap: HashMap<String, String> = HashMap::new();
+        hashmap.insert("market".to_string(), market);
+        hashmap.insert("address".to_string(), owner);
+        if let Some(limit) = limit {
+            hashmap.insert("limit".to_string(), limit.to_string());
+        }
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "GetOpenOrders".to_string(),
+            params: BloxrouteRequestParams::Hashmap(hashmap),
+        };
+
+        self.send_request(req_payload).await
+    }
+
+    pub async fn subscribe_to_openbook_get_unsettled(
+        &mut self,
+        id: String,
+        market: String,
+        owner: String,
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookGetUnsettledResponse>, BloxrouteError> {
+        let mut hashmap: HashMap<String, String> = HashMap::new();
+        hashmap.insert("market".to_string(), market);
+        hashmap.insert("ownerAddress".to_string(), owner);
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "GetUnsettled".to_string(),
+            params: BloxrouteRequestParams::Hashmap(hashmap),
+        };
+
+        self.send_request(req_payload).await
+    }
+
+    /// Streams the book of every market in the payload whenever it changes.
+    pub async fn subscribe_to_openbook_orderbooks_stream(
+        &mut self,
+        id: String,
+        payload: BloxrouteOpenbookOrderbooksStreamPayload,
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookOrderbookStreamResponse>, BloxrouteError> {
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "subscribe".to_string(),
+            params: BloxrouteRequestParams::Array(vec![
+                BloxrouteRequestParams::String("GetOrderbooksStream".to_string()),
+                BloxrouteRequestParams::Object(payload),
+            ]),
+        };
+
+        self.send_subscription(req_payload).await
+    }
+
+    /// Streams the best bid and ask of every market whenever they change.
+    pub async fn subscribe_to_openbook_tickers_stream(
+        &mut self,
+        id: String,
+        markets: Vec<String>,
+    ) -> Result<BloxrouteStream<BloxrouteOpenbookTickerStreamResponse>, BloxrouteError> {
+        let mut hashmap: HashMap<String, Vec<String>> = HashMap::new();
+        hashmap.insert("markets".to_string(), markets);
+
+        let req_payload = BloxrouteRequestPayload {
+            id,
+            jsonrpc: "2.0".to_string(),
+            method: "subscribe".to_string(),
+            params: BloxrouteRequestParams::Array(vec![
+                BloxrouteRequestParams::String("GetTickersStream".to_string()),
+                BloxrouteRequestParams::Object(hashmap),
+            ]),
+        };
+
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_stream_priority_fee(
@@ -227,7 +850,7 @@ impl BloxrouteWsClient {
         id: String,
         project: String,
         percentile: Option<f64>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteGetStreamPriorityFee>, BloxrouteError> {
         let mut hashmap: HashMap<String, String> = HashMap::new();
         hashmap.insert("project".to_string(), project.to_string());
         if let Some(percentile) = percentile {
@@ -243,14 +866,14 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_stream_pool_reserves(
         &mut self,
         id: String,
         pools: Vec<String>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteRaydiumStreamReservesResponse>, BloxrouteError> {
         let mut hashmap: HashMap<String, Vec<String>> = HashMap::new();
         hashmap.insert("pools".to_string(), pools);
 
@@ -264,14 +887,14 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_stream_swaps(
         &mut self,
         id: String,
         payload: BloxrouteRaydiumStreamSwapsPayload,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteRaydiumStreamSwapsResponse>, BloxrouteError> {
         let req_payload = BloxrouteRequestPayload {
             id,
             jsonrpc: "2.0".to_string(),
@@ -282,14 +905,14 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_new_raydium_pools(
         &mut self,
         id: String,
         include_cpmm: Option<bool>,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteRaydiumNewRaydiumPoolsResponse>, BloxrouteError> {
         let mut hashmap: HashMap<String, bool> = HashMap::new();
         if let Some(include_cpmm) = include_cpmm {
             hashmap.insert("includeCPMM".to_string(), include_cpmm);
@@ -305,13 +928,13 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
     pub async fn subscribe_to_stream_bundle_tip(
         &mut self,
         id: String,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    ) -> Result<BloxrouteStream<BloxrouteGetBundleTipStreamResponse>, BloxrouteError> {
         let hashmap: HashMap<String, String> = HashMap::new();
         let req_payload = BloxrouteRequestPayload {
             id,
@@ -323,105 +946,375 @@ impl BloxrouteWsClient {
             ]),
         };
 
-        return send_message(self.write.clone(), req_payload).await;
+        self.send_subscription(req_payload).await
     }
 
+    /// Spawns the task reading the socket, which reconnects whenever the socket closes or stops
+    /// answering pings. It stops once the client is closed or its last clone dropped.
     pub async fn init(&self) {
-        let read_clone = self.read.clone();
-        let tx_clone = self.tx.clone();
+        let client = self.downgrade();
+        let read = self.read.clone();
+        let mut shutdown = self.shutdown.subscribe();
         tokio::spawn(async move {
-            let read = read_clone.clone();
             let mut lock_guard = read.lock().await;
-            let tx = tx_clone.lock().await;
-            while let Some(data) = lock_guard.next().await {
-                if let Ok(text) = data {
-                    let text = &text.to_string();
-                    if let Ok(res) = serde_json::from_str::<BloxrouteSubscription>(text) {
-                        let _ = tx.send(BloxrouteResponseEnum::Subscription(res)).await;
-                    } else if let Ok(res) =
-                        serde_json::from_str::<BloxrouteGeneric<BloxrouteBlock>>(text)
-                    {
-                        let _ = tx.send(BloxrouteResponseEnum::Block(res)).await;
-                    } else if let Ok(res) =
-                        serde_json::from_str::<BloxrouteGeneric<BloxrouteTransaction>>(text)
-                    {
-                        let _ = tx.send(BloxrouteResponseEnum::Transaction(res)).await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGeneric<BloxrouteGetStreamPriorityFee>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::GetStreamPriorityFee(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGeneric<BloxrouteGetBundleTipStreamResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::GetBundleTipStream(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGenericSolana<BloxrouteOpenbookGetMarketsResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::OpenbookGetMarkets(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGenericSolana<BloxrouteOpenbookGetOrderbookResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::OpenbookGetOrderbookResponse(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGenericSolana<BloxrouteOpenbookGetDepthResponse>,
-                    >(text)
-                    {
-                        let _ = tx.send(BloxrouteResponseEnum::OpenbookGetDepth(res)).await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGenericSolana<BloxrouteOpenbookGetTickersResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::OpenbookGetTickers(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGeneric<BloxrouteRaydiumStreamReservesResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::RaydiumStreamReservesResponse(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGeneric<BloxrouteRaydiumStreamSwapsResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::RaydiumStreamSwapsResponse(res))
-                            .await;
-                    } else if let Ok(res) = serde_json::from_str::<
-                        BloxrouteGeneric<BloxrouteRaydiumNewRaydiumPoolsResponse>,
-                    >(text)
-                    {
-                        let _ = tx
-                            .send(BloxrouteResponseEnum::RaydiumNewRaydiumPoolsResponse(res))
-                            .await;
-                    } else {
-                        println!("{:#?}", text);
+            loop {
+                tokio::select! {
+                    _ = closed(&mut shutdown) => return,
+                    _ = client.read_until_disconnected(&mut lock_guard) => {}
+                }
+
+                println!("Disconnected from bloxroute, reconnecting");
+                let (write, read) = tokio::select! {
+                    _ = closed(&mut shutdown) => return,
+                    stream = open_socket_with_retry(&client.config) => match stream {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            println!("Giving up reconnecting to bloxroute: {}", err);
+                            // Ends every stream and fails every request still waiting for a
+                            // response.
+                            if let Some(client) = client.upgrade() {
+                                *client.routes.lock().await = BloxrouteRoutes::default();
+                            }
+                            return;
+                        }
+                    },
+                };
+                let Some(client) = client.upgrade() else {
+                    return;
+                };
+                println!("Successfully reconnected to bloxroute");
+                *lock_guard = read;
+                client.replay_subscriptions(write).await;
+            }
+        });
+    }
+
+    /// Swaps in the new socket and replays the subscriptions sent on the old one.
+    ///
+    /// Both locks are held throughout, so requests wait for the replay and are sent on the new
+    /// socket only. Subscriptions still waiting for their acknowledgement get it from the replay,
+    /// other requests sent on the old socket fail.
+    async fn replay_subscriptions(&self, new_write: WsWrite) {
+        let mut write = self.write.lock().await;
+        let mut routes = self.routes.lock().await;
+        *write = new_write;
+        let routes = &mut *routes;
+        routes.subscriptions.clear();
+        routes
+            .pending
+            .retain(|id, _| routes.payloads.contains_key(id));
+        routes.streams.retain(|_, route| !route.one_shot);
+        for (id, data) in &routes.payloads {
+            if let Err(err) = write.send(Message::Text(data.clone())).await {
+                println!("Error replaying subscription {}: {}", id, err);
+            }
+        }
+    }
+
+    /// Routes a frame to the stream registered for its subscription or request id, parsing the
+    /// envelope once and the payload straight into the type the route expects.
+    async fn dispatch_message(&self, text: &str) {
+        let envelope = match serde_json::from_str::<BloxrouteEnvelope>(text) {
+            Ok(envelope) => envelope,
+            Err(err) => {
+                println!("Error parsing message {}: {:#?}", err, text);
+                return;
+            }
+        };
+
+        let (request_id, sender, lagged, decode) = {
+            let mut routes = self.routes.lock().await;
+            if let Some(params) = &envelope.params {
+                let Some(request_id) = routes.subscriptions.get(&params.subscription).cloned()
+                else {
+                    return;
+                };
+                match routes.streams.get(&request_id) {
+                    Some(route) => (
+                        request_id,
+                        route.sender.clone(),
+                        route.lagged.clone(),
+                        route.decode,
+                    ),
+                    None => return,
+                }
+            } else if let Some(request_id) = envelope.id.clone() {
+                let waiter = routes.pending.remove(&request_id);
+                if let Some(err) = rpc_error(&envelope) {
+                    // The request was rejected, so whatever it opened is dead.
+                    routes.remove(&request_id);
+                    match waiter {
+                        Some(waiter) => {
+                            let _ = waiter.send(Err(err));
+                        }
+                        None => println!("Error response for {}: {}", request_id, err),
+                    }
+                    return;
+                }
+                match routes.streams.get(&request_id) {
+                    Some(route) if route.one_shot => {
+                        let (sender, lagged, decode) =
+                            (route.sender.clone(), route.lagged.clone(), route.decode);
+                        routes.remove(&request_id);
+                        if let Some(waiter) = waiter {
+                            // The response itself goes to the stream.
+                            let _ = waiter.send(Ok(Value::Null));
+                        }
+                        (request_id, sender, lagged, decode)
                     }
+                    Some(_) => {
+                        let subscription_id = envelope.result.and_then(subscription_id);
+                        if let Some(subscription_id) = &subscription_id {
+                            routes
+                                .subscriptions
+                                .insert(subscription_id.clone(), request_id);
+                        }
+                        if let Some(waiter) = waiter {
+                            let _ =
+                                waiter.send(Ok(subscription_id.map_or(Value::Null, Value::String)));
+                        }
+                        return;
+                    }
+                    None if waiter.is_some() => {
+                        let result = envelope
+                            .result
+                            .and_then(|result| serde_json::from_str(result.get()).ok());
+                        if let Some(waiter) = waiter {
+                            let _ = waiter.send(Ok(result.unwrap_or(Value::Null)));
+                        }
+                        return;
+                    }
+                    None => {
+                        // The stream was dropped before the gateway acknowledged it.
+                        if let Some(subscription_id) = envelope.result.and_then(subscription_id) {
+                            let client = self.clone();
+                            tokio::spawn(async move {
+                                client.cancel_subscription(subscription_id).await
+                            });
+                        }
+                        return;
+                    }
+                }
+            } else {
+                println!("{:#?}", text);
+                return;
+            }
+        };
+
+        match decode(&envelope) {
+            // Never wait on a slow consumer, it would hold up every other stream. A closed
+            // channel means the stream was dropped, `watch_dropped_streams` cleans it up.
+            Ok(response) => {
+                if let Err(TrySendError::Full(_)) = sender.try_send(response) {
+                    lagged.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(err) => println!("Error decoding message for {}: {}", request_id, err),
+        }
+    }
+}
+
+impl BloxrouteWsClientWeak {
+    /// Dispatches frames until the socket closes, fails or misses a ping.
+    async fn read_until_disconnected(&self, read: &mut WsRead) {
+        let mut awaiting_pong = false;
+        loop {
+            let frame = match self.config.ping_interval {
+                Some(interval) => match time::timeout(interval, read.next()).await {
+                    Ok(frame) => frame,
+                    Err(_) if awaiting_pong => {
+                        println!("No answer to ping from bloxroute");
+                        return;
+                    }
+                    Err(_) => {
+                        let Some(client) = self.upgrade() else {
+                            return;
+                        };
+                        let ping = Message::Ping(Vec::new());
+                        if let Err(err) = client.write.lock().await.send(ping).await {
+                            println!("Error pinging bloxroute {}", err);
+                            return;
+                        }
+                        awaiting_pong = true;
+                        continue;
+                    }
+                },
+                None => read.next().await,
+            };
+            // Any frame shows the connection is alive.
+            awaiting_pong = false;
+            match frame {
+                Some(Ok(Message::Text(text))) => match self.upgrade() {
+                    Some(client) => client.dispatch_message(&text).await,
+                    None => return,
+                },
+                Some(Ok(Message::Close(_))) | None => return,
+                Some(Ok(_)) => {}
+                Some(Err(err)) => {
+                    println!("Websocket error {}", err);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Unsubscribes the routes whose stream handle has been dropped.
+async fn watch_dropped_streams(
+    client: BloxrouteWsClientWeak,
+    mut dropped: UnboundedReceiver<String>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        let request_id = tokio::select! {
+            _ = closed(&mut shutdown) => return,
+            request_id = dropped.recv() => request_id,
+        };
+        match (request_id, client.upgrade()) {
+            (Some(request_id), Some(client)) => client.unsubscribe_dropped(request_id).await,
+            _ => return,
+        }
+    }
+}
+
+/// Resolves once the client is closed or its last clone dropped.
+async fn closed(shutdown: &mut watch::Receiver<bool>) {
+    let _ = shutdown.wait_for(|closed| *closed).await;
+}
+
+/// Opens the socket, backing off exponentially between attempts while the failure is retryable.
+async fn open_socket_with_retry(
+    config: &BloxrouteClientBuilder,
+) -> Result<(WsWrite, WsRead), BloxrouteError> {
+    let policy = &config.reconnect_policy;
+    let mut backoff = policy.initial_backoff;
+    let mut attempts = 0;
+    loop {
+        match open_socket(config).await {
+            Ok(stream) => return Ok(stream),
+            Err(err) => {
+                attempts += 1;
+                if !err.is_retryable() || policy.max_attempts.is_some_and(|max| attempts >= max) {
+                    return Err(err);
                 }
+                println!("Error connecting to websocket {}", err);
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+            }
+        }
+    }
+}
+
+pub(crate) async fn open_socket(
+    config: &BloxrouteClientBuilder,
+) -> Result<(WsWrite, WsRead), BloxrouteError> {
+    let handshake = async {
+        let mut req = config.endpoint.as_str().into_client_request()?;
+        let headers = req.headers_mut();
+        headers.append("Authorization", config.auth_header.parse()?);
+        if let Some(user_agent) = &config.user_agent {
+            let user_agent = HeaderValue::from_str(user_agent)
+                .map_err(|err| tungstenite::Error::HttpFormat(err.into()))?;
+            headers.append(USER_AGENT, user_agent);
+        }
+        let connector = tls_connector(&config.tls)?;
+        let (stream, _) = match &config.proxy {
+            Some(proxy) => {
+                let tunnel = proxy_tunnel(proxy, req.uri()).await?;
+                client_async_tls_with_config(req, tunnel, None, connector).await?
             }
+            None => connect_async_tls_with_config(req, None, false, connector).await?,
+        };
+        Ok(stream.split())
+    };
+    match config.connect_timeout {
+        Some(timeout) => time::timeout(timeout, handshake)
+            .await
+            .map_err(|_| BloxrouteError::Timeout)?,
+        None => handshake.await,
+    }
+}
+
+/// Builds a connector from the TLS options, `None` keeps the default one.
+fn tls_connector(tls: &BloxrouteTlsOptions) -> Result<Option<Connector>, BloxrouteError> {
+    if tls.root_certificates.is_empty() && !tls.danger_accept_invalid_certs {
+        return Ok(None);
+    }
+    let mut builder = native_tls::TlsConnector::builder();
+    builder.danger_accept_invalid_certs(tls.danger_accept_invalid_certs);
+    for pem in &tls.root_certificates {
+        builder.add_root_certificate(native_tls::Certificate::from_pem(pem)?);
+    }
+    Ok(Some(Connector::NativeTls(builder.build()?)))
+}
+
+/// Opens a TCP tunnel to the endpoint's host through an HTTP proxy.
+async fn proxy_tunnel(proxy: &str, target: &Uri) -> Result<TcpStream, BloxrouteError> {
+    let proxy: Uri = proxy
+        .parse()
+        .map_err(|err: tungstenite::http::uri::InvalidUri| {
+            tungstenite::Error::HttpFormat(err.into())
+        })?;
+    let proxy_host = proxy.host().unwrap_or_default();
+    let proxy_port = proxy.port_u16().unwrap_or(80);
+    let host = target.host().unwrap_or_default();
+    let port = target
+        .port_u16()
+        .unwrap_or(if target.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+    let request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read byte by byte so nothing past the proxy's answer is consumed.
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_PROXY_RESPONSE {
+            return Err(BloxrouteError::ConnectionClosed);
+        }
+        response.push(stream.read_u8().await?);
+    }
+    let response = String::from_utf8_lossy(&response).to_string();
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .unwrap_or(0);
+    if status != 200 {
+        return Err(BloxrouteError::Http {
+            status,
+            body: response,
         });
     }
+    Ok(stream)
+}
+
+/// Parses the error object of a JSON-RPC error response.
+fn rpc_error(envelope: &BloxrouteEnvelope) -> Option<BloxrouteError> {
+    let error = envelope.error?;
+    Some(
+        match serde_json::from_str::<BloxrouteJsonRpcError>(error.get()) {
+            Ok(error) => error.into(),
+            Err(err) => BloxrouteError::deserialization(err, error.get()),
+        },
+    )
+}
+
+/// Extracts the subscription id from a `subscribe` acknowledgement.
+fn subscription_id(result: &RawValue) -> Option<String> {
+    serde_json::from_str::<String>(result.get()).ok()
 }
 
 pub async fn send_message<T: Serialize>(
-    write: Arc<Mutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>,
+    write: Arc<Mutex<WsWrite>>,
     req_payload: T,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+) -> Result<(), BloxrouteError> {
     let data = Message::Text(json!(req_payload).to_string());
     let mut lock_guard = write.lock().await;
-    let _ = lock_guard.send(data).await;
-    return Ok(());
+    lock_guard.send(data).await?;
+    Ok(())
 }
@@ -1865,8 +1865,7 @@ pub const TX_TYPE_1559_1: &str = r#"{
   "jsonrpc": "2.0"
 }"#;
 
-
-pub const TX_TYPE_1559_2 : &str = r#"{
+pub const TX_TYPE_1559_2: &str = r#"{
   "method": "subscribe",
   "params": {
     "subscription": "aa9536b2-30c0-41bb-b9bb-950a6442a22b",
@@ -1890,4 +1889,327 @@ pub const TX_TYPE_1559_2 : &str = r#"{
     }
   },
   "jsonrpc": "2.0"
-}"#;
\ No newline at end of file
+}"#;
+
+pub const SUBSCRIPTION_ACK: &str = r#"{
+  "id": "newTxsId",
+  "result": "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+  "jsonrpc": "2.0"
+}"#;
+
+pub const OPENBOOK_GET_MARKETS: &str = r#"{
+  "id": "getMarketsId",
+  "result": {
+    "markets": {
+      "SOL/USDC": {
+        "market": "SOL/USDC",
+        "status": "MS_ONLINE",
+        "address": "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6",
+        "baseMint": "So11111111111111111111111111111111111111112",
+        "quotedMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "baseDecimals": "9",
+        "quoteDecimals": "6",
+        "project": "P_OPENBOOK"
+      }
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const TX_STATUS: &str = r#"{
+  "method": "subscribe",
+  "params": {
+    "subscription": "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+    "result": {
+      "txHash": "0x0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e",
+      "status": "mined",
+      "blockHash": "0xef34292541b52caa938e3d9295d635d80a79bb219518cd120b60fdd1b6a674af",
+      "blockNumber": "0x13a5b7c"
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const TX_RECEIPT: &str = r#"{
+  "method": "subscribe",
+  "params": {
+    "subscription": "c7beecd7-7fb8-4e3f-a316-eeda8db4d345",
+    "result": {
+      "blockHash": "0xef34292541b52caa938e3d9295d635d80a79bb219518cd120b60fdd1b6a674af",
+      "blockNumber": "0x13a5b7c",
+      "contractAddress": null,
+      "cumulativeGasUsed": "0x1a8b4",
+      "effectiveGasPrice": "0x4a817c800",
+      "from": "0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430",
+      "gasUsed": "0x1a8b4",
+      "logs": [
+        {
+          "address": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+          "topics": [
+            "0xe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c",
+            "0x0000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488d"
+          ],
+          "data": "0x000000000000000000000000000000000000000000000000016345785d8a0000",
+          "blockNumber": "0x13a5b7c",
+          "transactionHash": "0x0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e",
+          "transactionIndex": "0x0",
+          "blockHash": "0xef34292541b52caa938e3d9295d635d80a79bb219518cd120b60fdd1b6a674af",
+          "logIndex": "0x0",
+          "removed": false
+        }
+      ],
+      "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+      "status": "0x1",
+      "to": "0x00000047bb99ea4d791bb749d970de71ee0b1a34",
+      "transactionHash": "0x0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e",
+      "transactionIndex": "0x0",
+      "type": "0x2",
+      "blobGasUsed": null,
+      "txsCount": "0x9c"
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const BUNDLE_SIMULATION: &str = r#"{
+  "id": "1",
+  "result": {
+    "bundleGasPrice": "2000000000",
+    "bundleHash": "0x73b1e258c7a42fd0230b2fd05529c5d4b6fcb66c227783f8bece8aeacdd1db2e",
+    "coinbaseDiff": "4200000000000000",
+    "ethSentToCoinbase": "0",
+    "gasFees": "4200000000000000",
+    "results": [
+      {
+        "coinbaseDiff": "4200000000000000",
+        "ethSentToCoinbase": "0",
+        "fromAddress": "0x02A727155aeF8609c9f7F2179b2a1f560B39F5A0",
+        "gasFees": "4200000000000000",
+        "gasPrice": "200000000000",
+        "gasUsed": 21000,
+        "toAddress": "0x73625f59CAdc5009Cb458B751b3E7b6b48C06f2C",
+        "txHash": "0x669b4704a7d993a946cdd6e2f95233f308ce0c4649d2e04944e8299efcaa098a",
+        "value": "0x"
+      },
+      {
+        "coinbaseDiff": "0",
+        "ethSentToCoinbase": "0",
+        "fromAddress": "0x02A727155aeF8609c9f7F2179b2a1f560B39F5A0",
+        "gasFees": "0",
+        "gasPrice": "200000000000",
+        "gasUsed": 30000,
+        "toAddress": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+        "txHash": "0xa839ee83465657cac01adc1d50d96c1b586ed498120a84a64749c0034b4f19fa",
+        "error": "execution reverted",
+        "revert": "transfer amount exceeds balance"
+      }
+    ],
+    "stateBlockNumber": 5221585,
+    "totalGasUsed": 51000
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const TX_TYPE_7702: &str = r#"{
+  "method": "subscribe",
+  "params": {
+    "subscription": "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+    "result": {
+      "txHash": "0x7c1a3b1fa5e77f3e2bd3b0b5a1a9e6a4c4b7d0d0c1f6b0f4e8a5d4e2c1b3a291",
+      "txContents": {
+        "accessList": [],
+        "authorizationList": [
+          {
+            "chainId": "0x1",
+            "address": "0x63c0c19a282a1b52b07dd5a65b58948a07dae32b",
+            "nonce": "0x5",
+            "yParity": "0x1",
+            "r": "0x8e1a54d8d0b1c4c7f1a2c5f0d6a4f6a1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7",
+            "s": "0x1f2e3d4c5b6a79881726354453627180e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5b4"
+          }
+        ],
+        "chainId": "0x1",
+        "from": "0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430",
+        "gas": "0x186a0",
+        "gasPrice": null,
+        "hash": "0x7c1a3b1fa5e77f3e2bd3b0b5a1a9e6a4c4b7d0d0c1f6b0f4e8a5d4e2c1b3a291",
+        "input": "0x",
+        "maxFeePerGas": "0x6c4376b9",
+        "maxPriorityFeePerGas": "0x257306f3",
+        "nonce": "0x2c",
+        "r": "0x3b2f4c1a8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a3928170605f4e3",
+        "s": "0x4a3928170605f4e3d2c1b0a9f8e7d6c5b4a3928170605f4e3d2c1b0a9f8e7d6",
+        "to": "0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430",
+        "type": "0x4",
+        "v": "0x0",
+        "value": "0x0",
+        "yParity": "0x0"
+      },
+      "localRegion": true
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const OPENBOOK_GET_OPEN_ORDERS: &str = r#"{
+  "id": "openOrdersId",
+  "result": {
+    "orders": [
+      {
+        "orderID": "2770756446290057010245906",
+        "market": "SOL/USDC",
+        "side": "S_ASK",
+        "types": ["OT_LIMIT"],
+        "price": 150.5,
+        "remainingSize": 0.1,
+        "createdAt": null,
+        "clientOrderID": "42",
+        "openOrderAccount": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN"
+      }
+    ]
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const OPENBOOK_GET_UNSETTLED: &str = r#"{
+  "id": "unsettledId",
+  "result": {
+    "market": "SOL/USDC",
+    "unsettled": [
+      {
+        "account": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN",
+        "baseToken": "So11111111111111111111111111111111111111112",
+        "quoteToken": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "baseTokenAmount": 0.0,
+        "quoteTokenAmount": 15.05
+      }
+    ]
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const OPENBOOK_ORDERBOOKS_STREAM: &str = r#"{
+  "method": "subscribe",
+  "params": {
+    "subscription": "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+    "result": {
+      "slot": "250114623",
+      "orderbook": {
+        "market": "SOL/USDC",
+        "marketAddress": "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6",
+        "bids": [
+          {
+            "price": 149.81,
+            "size": 12.5,
+            "orderID": "2763504424575880445837148",
+            "clientOrderID": "0",
+            "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ"
+          }
+        ],
+        "asks": [
+          {
+            "price": 149.86,
+            "size": 3.1,
+            "orderID": "2764426800099112419553304",
+            "clientOrderID": "42",
+            "ownerAddress": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN"
+          }
+        ]
+      }
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const OPENBOOK_TICKERS_STREAM: &str = r#"{
+  "method": "subscribe",
+  "params": {
+    "subscription": "8c2a1f4e-3b7d-4e59-9a61-2f0d5c7e9b13",
+    "result": {
+      "slot": 250114624,
+      "ticker": {
+        "market": "SOL/USDC",
+        "marketAddress": "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6",
+        "bid": 149.81,
+        "bidSize": 12.5,
+        "ask": 149.86,
+        "askSize": 3.1,
+        "project": "P_OPENBOOK"
+      }
+    }
+  },
+  "jsonrpc": "2.0"
+}"#;
+
+pub const JUPITER_QUOTE: &str = r#"{
+  "inToken": "SOL",
+  "inTokenAddress": "So11111111111111111111111111111111111111112",
+  "outToken": "USDC",
+  "outTokenAddress": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+  "inAmount": 0.1,
+  "routes": [
+    {
+      "inAmount": 0.1,
+      "outAmount": 15.05,
+      "outAmountMin": 14.97,
+      "steps": [
+        {
+          "inToken": "SOL",
+          "inTokenAddress": "So11111111111111111111111111111111111111112",
+          "outToken": "USDC",
+          "outTokenAddress": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+          "inAmount": 0.1,
+          "outAmount": 15.05,
+          "slippage": 0.5,
+          "priceImpactPercent": {
+            "percent": 0.01,
+            "infinity": "INF_NOT"
+          },
+          "fee": {
+            "amount": 0.00025,
+            "mint": "So11111111111111111111111111111111111111112",
+            "percent": 0.0025
+          },
+          "outAmountMin": 14.97,
+          "project": {
+            "label": "Whirlpool",
+            "id": "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+          }
+        }
+      ]
+    }
+  ]
+}"#;
+
+pub const JUPITER_SWAP: &str = r#"{
+  "outAmount": 15.05,
+  "outAmountMin": 14.97,
+  "priceImpact": {
+    "percent": 0.01,
+    "infinity": "INF_NOT"
+  },
+  "fees": [
+    {
+      "amount": 0.00025,
+      "mint": "So11111111111111111111111111111111111111112",
+      "percent": 0.0025
+    }
+  ],
+  "transactions": [
+    {
+      "content": "AQAB",
+      "isCleanup": false
+    }
+  ]
+}"#;
+
+pub const JUPITER_ROUTE_SWAP: &str = r#"{
+  "outAmount": 15.05,
+  "outAmountMin": 14.97,
+  "transactions": [
+    {
+      "content": "AQAC",
+      "isCleanup": false
+    }
+  ]
+}"#;
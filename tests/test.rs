@@ -2,28 +2,96 @@ pub mod mock;
 
 #[cfg(test)]
 mod tests {
-    use crate::mock::{BLOCK_DATA, TX_TYPE_1559_1, TX_TYPE_1559_2};
-    use bloxroute_sdk::models::{
-        block::BloxrouteBlock, transaction::BloxrouteTransaction, BloxrouteGeneric,
+    use crate::mock::{
+        BLOCK_DATA, BUNDLE_SIMULATION, JUPITER_QUOTE, JUPITER_ROUTE_SWAP, JUPITER_SWAP,
+        OPENBOOK_GET_MARKETS, OPENBOOK_GET_OPEN_ORDERS, OPENBOOK_GET_UNSETTLED,
+        OPENBOOK_ORDERBOOKS_STREAM, OPENBOOK_TICKERS_STREAM, SUBSCRIPTION_ACK, TX_RECEIPT,
+        TX_STATUS, TX_TYPE_1559_1, TX_TYPE_1559_2, TX_TYPE_7702,
+    };
+    use alloy_primitives::{address, b256, uint, U256};
+    use bloxroute_sdk::{
+        error::BloxrouteError,
+        models::{
+            block::BloxrouteBlock,
+            bundle::{BloxrouteBundle, BloxrouteSimulateBundleRequest},
+            eth_on_block::{
+                BloxrouteEthOnBlockCall, BloxrouteEthOnBlockRequest, BLOCK_HEIGHT_PLACEHOLDER,
+            },
+            filter::{BloxrouteFilter, BloxrouteFilterField},
+            openbook::{
+                BloxrouteOpenbookCancelOrderPayload, BloxrouteOpenbookGetDepthResponse,
+                BloxrouteOpenbookOrderPayload, BloxrouteOpenbookOrderRef,
+                BloxrouteOpenbookOrderType, BloxrouteOpenbookOrderbookStreamResponse,
+                BloxrouteOpenbookOrderbooksStreamPayload, BloxrouteOpenbookSettlePayload,
+                BloxrouteOpenbookSide,
+            },
+            orderbook::{BloxrouteOrderBook, BloxrouteOrderBookUpdate},
+            raw_tx::decode_raw_transaction_hex,
+            receipt::BloxrouteTxReceipt,
+            solana::{QuoteStepProject, RouteStep, RouteSwapPayload, SwapPayload},
+            transaction::{
+                BloxrouteBlockRequestInclude, BloxrouteCancelPrivateTransactionRequest,
+                BloxrouteSendBatchTransactionsRequest, BloxrouteSendPrivateTransactionRequest,
+                BloxrouteSendTransactionRequest, BloxrouteTransaction,
+                BloxrouteTransactionContents, BloxrouteTransactionRequestInclude,
+                BloxrouteTransactionStatusKind, BloxrouteTransactionStatusRequest,
+            },
+            BloxrouteGeneric,
+        },
+        providers::{
+            builder::{BloxrouteClientBuilder, BloxrouteReconnectPolicy},
+            endpoint::{select_fastest_endpoint, BloxrouteEndpoint, BloxrouteEvmRegion},
+            http::BloxrouteHttpClient,
+            multi::BloxrouteMultiWsClient,
+            ws::BloxrouteWsClient,
+        },
+    };
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::json;
+    use std::{collections::HashMap, time::Duration};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc,
+    };
+    use tokio_tungstenite::{
+        accept_async, accept_hdr_async,
+        tungstenite::{
+            handshake::server::{Request, Response},
+            Message,
+        },
     };
 
     #[tokio::test]
     async fn destruct_blocks() {
         let response_json: BloxrouteGeneric<BloxrouteBlock> =
             serde_json::from_str(BLOCK_DATA).unwrap();
+        let header = response_json.params.result.header;
         assert_eq!(
-            response_json.params.result.header.miner,
-            "0x388c818ca8b9251b393131c08a736a67ccb19297"
+            header.miner,
+            address!("388c818ca8b9251b393131c08a736a67ccb19297")
         );
+        assert_eq!(header.number, 0x138c9d6);
+        assert_eq!(header.baseFeePerGas, 1497783322);
     }
 
     #[tokio::test]
     async fn destruct_transactions_1() {
         let response_json: BloxrouteGeneric<BloxrouteTransaction> =
             serde_json::from_str(TX_TYPE_1559_1).unwrap();
+        let contents = response_json.params.result.txContents.unwrap();
+        let BloxrouteTransactionContents::Eip1559(tx) = contents else {
+            panic!("unexpected transaction {:?}", contents);
+        };
+        assert_eq!(
+            tx.common.s.unwrap(),
+            uint!(0x5ca022fabdbfbda632c037cd22ed0e63627b7de4b8268aed8d196929687fe07e_U256)
+        );
+        assert_eq!(tx.common.nonce, Some(0x2b));
+        assert_eq!(tx.gasPrice, None);
         assert_eq!(
-            response_json.params.result.txContents.unwrap().s.unwrap(),
-            "0x5ca022fabdbfbda632c037cd22ed0e63627b7de4b8268aed8d196929687fe07e"
+            tx.common.value,
+            Some(U256::from(100_000_000_000_000_000u64))
         );
     }
 
@@ -32,8 +100,1810 @@ mod tests {
         let response_json: BloxrouteGeneric<BloxrouteTransaction> =
             serde_json::from_str(TX_TYPE_1559_2).unwrap();
         assert_eq!(
-            response_json.params.result.txContents.unwrap().r.unwrap(),
-            "0x259a397807739bc3a0531ceb51b6af58b8302417f5531c8bf4d02b9d16c2d3a9"
+            response_json
+                .params
+                .result
+                .txContents
+                .unwrap()
+                .common()
+                .r
+                .unwrap(),
+            uint!(0x259a397807739bc3a0531ceb51b6af58b8302417f5531c8bf4d02b9d16c2d3a9_U256)
+        );
+    }
+
+    /// Every field serialized back from a fixture transaction has the value it was read from.
+    fn assert_round_trip(raw: &serde_json::Value) -> BloxrouteTransactionContents {
+        let tx: BloxrouteTransactionContents = serde_json::from_value(raw.clone()).unwrap();
+        let serialized = serde_json::to_value(&tx).unwrap();
+        for (field, value) in serialized.as_object().unwrap() {
+            assert_eq!(&raw[field], value, "field {}", field);
+        }
+        assert_eq!(
+            serde_json::from_value::<BloxrouteTransactionContents>(serialized).unwrap(),
+            tx
+        );
+        tx
+    }
+
+    #[tokio::test]
+    async fn round_trips_all_transaction_types() {
+        let block: serde_json::Value = serde_json::from_str(BLOCK_DATA).unwrap();
+        let mut types = vec![];
+        for raw in block["params"]["result"]["transactions"]
+            .as_array()
+            .unwrap()
+        {
+            match assert_round_trip(raw) {
+                BloxrouteTransactionContents::Eip4844(tx) => {
+                    assert!(!tx.blobVersionedHashes.unwrap().is_empty());
+                    assert!(tx.maxFeePerBlobGas.is_some());
+                    types.push(3);
+                }
+                tx => types.push(tx.tx_type()),
+            }
+        }
+        assert!([0, 2, 3].iter().all(|tx_type| types.contains(tx_type)));
+
+        for fixture in [TX_TYPE_1559_1, TX_TYPE_1559_2, TX_TYPE_7702] {
+            let tx: serde_json::Value = serde_json::from_str(fixture).unwrap();
+            assert_round_trip(&tx["params"]["result"]["txContents"]);
+        }
+        let tx: BloxrouteGeneric<BloxrouteTransaction> =
+            serde_json::from_str(TX_TYPE_7702).unwrap();
+        let BloxrouteTransactionContents::Eip7702(tx) = tx.params.result.txContents.unwrap() else {
+            panic!("expected an EIP-7702 transaction");
+        };
+        assert_eq!(tx.authorizationList.unwrap()[0].nonce, 5);
+
+        // Without `type`, e.g. when only some `tx_contents` fields are included.
+        let tx: BloxrouteTransactionContents =
+            serde_json::from_value(json!({ "nonce": "0x2b", "maxFeePerGas": "0x6c4376b9" }))
+                .unwrap();
+        assert_eq!(tx.tx_type(), 2);
+        let tx: BloxrouteTransactionContents =
+            serde_json::from_value(json!({ "value": 1 })).unwrap();
+        assert_eq!(tx.common().value, Some(U256::from(1)));
+    }
+
+    #[tokio::test]
+    async fn decodes_raw_transactions() {
+        for fixture in [TX_TYPE_1559_1, TX_TYPE_1559_2] {
+            let response_json: BloxrouteGeneric<BloxrouteTransaction> =
+                serde_json::from_str(fixture).unwrap();
+            let tx = response_json.params.result;
+            let decoded = tx.decode_raw_tx().unwrap();
+            assert_eq!(decoded.common().hash.unwrap(), tx.txHash);
+
+            let decoded = serde_json::to_value(decoded).unwrap();
+            let contents = serde_json::to_value(tx.txContents.unwrap()).unwrap();
+            for (field, value) in contents.as_object().unwrap() {
+                if !value.is_null() {
+                    assert_eq!(&decoded[field], value, "field {}", field);
+                }
+            }
+        }
+
+        // EIP-155 example transaction.
+        let tx = decode_raw_transaction_hex("0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        assert_eq!(tx.common().chainId, Some(1));
+        assert_eq!(
+            tx.common().from,
+            Some(address!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"))
+        );
+
+        assert!(matches!(
+            decode_raw_transaction_hex("0x02c0"),
+            Err(BloxrouteError::RawTransaction(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn destruct_tx_receipt() {
+        let response_json: BloxrouteGeneric<BloxrouteTxReceipt> =
+            serde_json::from_str(TX_RECEIPT).unwrap();
+        let receipt = response_json.params.result;
+        assert!(receipt.succeeded());
+        assert_eq!(
+            receipt.blockHash,
+            b256!("ef34292541b52caa938e3d9295d635d80a79bb219518cd120b60fdd1b6a674af")
+        );
+        assert_eq!(receipt.blockNumber, 0x13a5b7c);
+        assert_eq!(
+            receipt.from,
+            Some(address!("6c4c024d1c9d3f3a13fed0aa8930894e2cd31430"))
+        );
+        assert_eq!(
+            receipt.to,
+            Some(address!("00000047bb99ea4d791bb749d970de71ee0b1a34"))
+        );
+        assert_eq!(receipt.gasUsed, 0x1a8b4);
+        assert_eq!(receipt.effectiveGasPrice, Some(20_000_000_000));
+        assert_eq!(receipt.r#type, Some(2));
+        assert_eq!(receipt.txsCount, Some(156));
+        assert!(receipt.contractAddress.is_none());
+        assert!(receipt.blobGasUsed.is_none());
+
+        let log = &receipt.logs[0];
+        assert_eq!(
+            log.address,
+            address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+        );
+        assert_eq!(
+            log.topics,
+            vec![
+                b256!("e1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c"),
+                b256!("0000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488d")
+            ]
+        );
+        assert_eq!(
+            U256::from_be_slice(&log.data),
+            uint!(100_000_000_000_000_000_U256)
+        );
+        assert_eq!(log.transactionHash, Some(receipt.transactionHash));
+        assert_eq!(log.logIndex, Some(0));
+    }
+
+    #[tokio::test]
+    async fn reconnect_replays_subscriptions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let mut received = vec![];
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    received.push(text);
+                }
+                ws.send(Message::Text(SUBSCRIPTION_ACK.to_string()))
+                    .await
+                    .unwrap();
+                ws.send(Message::Text(TX_TYPE_1559_1.to_string()))
+                    .await
+                    .unwrap();
+                ws.close(None).await.unwrap();
+            }
+            received
+        });
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let tx = stream.next().await.unwrap();
+            assert_eq!(
+                tx.txHash,
+                b256!("0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e")
+            );
+        }
+
+        let received = server.await.unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0], received[1]);
+    }
+
+    #[tokio::test]
+    async fn acknowledges_subscriptions_pending_across_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut received = vec![];
+            // Drops the first connection before acknowledging.
+            for ack in [false, true] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    received.push(text);
+                }
+                if ack {
+                    ws.send(Message::Text(SUBSCRIPTION_ACK.to_string()))
+                        .await
+                        .unwrap();
+                    ws.send(Message::Text(TX_TYPE_1559_1.to_string()))
+                        .await
+                        .unwrap();
+                    while ws.next().await.is_some() {}
+                }
+            }
+            received
+        });
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        assert_eq!(
+            stream.subscription_id.as_deref(),
+            Some("5d50c6c1-e752-4439-8dbf-0f9e4406761c")
+        );
+        assert!(stream.next().await.is_some());
+
+        client.close().await.unwrap();
+        let received = server.await.unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0], received[1]);
+    }
+
+    #[tokio::test]
+    async fn closes_explicitly_and_when_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                while let Some(Ok(message)) = ws.next().await {
+                    if let Message::Text(text) = message {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let ack =
+                            SUBSCRIPTION_ACK.replace("newTxsId", request["id"].as_str().unwrap());
+                        ws.send(Message::Text(ack)).await.unwrap();
+                    }
+                }
+            }
+        });
+
+        let mut client = BloxrouteWsClient::connect(endpoint.clone(), "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        client.close().await.unwrap();
+        assert!(client.is_closed());
+        assert!(stream.next().await.is_none());
+
+        // Dropping the client ends the connection, so the server moves on and returns.
+        let client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reconnects_when_pings_go_unanswered() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            // Never reads, so pings stay unanswered.
+            let (stream, _) = listener.accept().await.unwrap();
+            let _half_open = accept_async(stream).await.unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+            accept_async(stream).await.unwrap();
+        });
+
+        let _client = BloxrouteClientBuilder::new(endpoint, "auth")
+            .ping_interval(Duration::from_millis(50))
+            .connect_ws()
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    /// Accepts one connection and answers every text frame with the frames returned by `handler`.
+    /// Each received frame is forwarded on the returned channel.
+    async fn spawn_server(
+        handler: fn(&serde_json::Value) -> Vec<String>,
+    ) -> (String, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Text(text) = message {
+                    let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    for frame in handler(&request) {
+                        ws.send(Message::Text(frame)).await.unwrap();
+                    }
+                    let _ = tx.send(request);
+                }
+            }
+        });
+        (endpoint, rx)
+    }
+
+    fn new_txs_params() -> BloxrouteTransactionRequestInclude {
+        BloxrouteTransactionRequestInclude {
+            include: None,
+            duplicates: None,
+            include_from_blockchain: None,
+            filters: None,
+            blockchain_network: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_messages_by_subscription() {
+        let (endpoint, _requests) = spawn_server(|request| match request["id"].as_str() {
+            Some("newTxsId") => vec![SUBSCRIPTION_ACK.to_string(), TX_TYPE_1559_1.to_string()],
+            Some("newBlocksId") => vec![
+                r#"{"id":"newBlocksId","result":"c7beecd7-7fb8-4e3f-a316-eeda8db4d345","jsonrpc":"2.0"}"#.to_string(),
+                BLOCK_DATA.to_string(),
+            ],
+            _ => vec![],
+        })
+        .await;
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut txs = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        let params = BloxrouteBlockRequestInclude {
+            include: None,
+            blockchain_network: None,
+        };
+        let mut blocks = client
+            .subscribe_to_new_blocks("newBlocksId".to_string(), params)
+            .await
+            .unwrap();
+
+        let block = blocks.next().await.unwrap();
+        assert_eq!(
+            block.hash,
+            b256!("ef34292541b52caa938e3d9295d635d80a79bb219518cd120b60fdd1b6a674af")
+        );
+        let tx = txs.next().await.unwrap();
+        assert_eq!(
+            tx.txHash,
+            b256!("0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e")
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_stream_does_not_hold_up_others() {
+        let (endpoint, _requests) = spawn_server(|request| match request["id"].as_str() {
+            Some("newTxsId") => vec![
+                SUBSCRIPTION_ACK.to_string(),
+                TX_TYPE_1559_1.to_string(),
+                TX_TYPE_1559_1.to_string(),
+                TX_TYPE_1559_1.to_string(),
+            ],
+            Some("newBlocksId") => vec![
+                r#"{"id":"newBlocksId","result":"c7beecd7-7fb8-4e3f-a316-eeda8db4d345","jsonrpc":"2.0"}"#.to_string(),
+                BLOCK_DATA.to_string(),
+            ],
+            _ => vec![],
+        })
+        .await;
+
+        let mut client = BloxrouteClientBuilder::new(endpoint, "auth")
+            .channel_capacity(1)
+            .request_timeout(Duration::from_secs(1))
+            .connect_ws()
+            .await
+            .unwrap();
+        // Never polled until the end.
+        let mut txs = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        let params = BloxrouteBlockRequestInclude {
+            include: None,
+            blockchain_network: None,
+        };
+        let mut blocks = client
+            .subscribe_to_new_blocks("newBlocksId".to_string(), params)
+            .await
+            .unwrap();
+        assert!(blocks.next().await.is_some());
+
+        assert_eq!(txs.lagged(), 2);
+        assert!(txs.next().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn unsubscribes_explicitly_and_on_drop() {
+        let (endpoint, mut requests) = spawn_server(|request| match request["method"].as_str() {
+            Some("subscribe") => vec![SUBSCRIPTION_ACK.to_string()],
+            Some("unsubscribe") => {
+                vec![json!({"id": request["id"], "result": true, "jsonrpc": "2.0"}).to_string()]
+            }
+            _ => vec![],
+        })
+        .await;
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let subscription_id = "5d50c6c1-e752-4439-8dbf-0f9e4406761c";
+
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        requests.recv().await.unwrap();
+        assert_eq!(stream.subscription_id.as_deref(), Some(subscription_id));
+        assert!(client.unsubscribe(&stream.id).await.unwrap());
+        assert_eq!(requests.recv().await.unwrap()["params"][0], subscription_id);
+        assert!(stream.next().await.is_none());
+        // Nothing left to cancel.
+        assert!(!client.unsubscribe(&stream.id).await.unwrap());
+
+        let stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        requests.recv().await.unwrap();
+        assert_eq!(stream.subscription_id.as_deref(), Some(subscription_id));
+        drop(stream);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request["method"], "unsubscribe");
+        assert_eq!(request["params"][0], subscription_id);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_request_id_cancels_the_previous_subscription() {
+        let (endpoint, mut requests) = spawn_server(|request| {
+            let subscription_id = match request["params"][0].as_str() {
+                Some("newTxs") => "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+                Some("pendingTxs") => "aa9536b2-30c0-41bb-b9bb-950a6442a22b",
+                _ => {
+                    return vec![
+                        json!({"id": request["id"], "result": true, "jsonrpc": "2.0"}).to_string(),
+                    ]
+                }
+            };
+            vec![
+                json!({"id": request["id"], "result": subscription_id, "jsonrpc": "2.0"})
+                    .to_string(),
+            ]
+        })
+        .await;
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut txs = client
+            .subscribe_to_new_txs("txsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        let _pending = client
+            .subscribe_to_pending_txs("txsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        assert!(txs.next().await.is_none());
+
+        let mut unsubscribed = vec![];
+        for _ in 0..3 {
+            let request = requests.recv().await.unwrap();
+            if request["method"] == "unsubscribe" {
+                unsubscribed.push(request["params"][0].clone());
+            }
+        }
+        assert_eq!(
+            unsubscribed,
+            vec![json!("5d50c6c1-e752-4439-8dbf-0f9e4406761c")]
+        );
+    }
+
+    #[tokio::test]
+    async fn routes_one_shot_responses_by_request_id() {
+        let (endpoint, _requests) = spawn_server(|request| match request["method"].as_str() {
+            Some("GetMarkets") => vec![OPENBOOK_GET_MARKETS.to_string()],
+            _ => vec![],
+        })
+        .await;
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut stream = client
+            .subscribe_to_openbook_get_markets("getMarketsId".to_string())
+            .await
+            .unwrap();
+
+        let markets = stream.next().await.unwrap();
+        assert_eq!(markets.markets["SOL/USDC"].project, "P_OPENBOOK");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn queries_openbook_open_orders_and_unsettled() {
+        let (endpoint, mut requests) = spawn_server(|request| match request["method"].as_str() {
+            Some("GetOpenOrders") => vec![OPENBOOK_GET_OPEN_ORDERS.to_string()],
+            Some("GetUnsettled") => vec![OPENBOOK_GET_UNSETTLED.to_string()],
+            _ => vec![],
+        })
+        .await;
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let owner = "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string();
+
+        let mut stream = client
+            .subscribe_to_openbook_get_open_orders(
+                "openOrdersId".to_string(),
+                "SOL/USDC".to_string(),
+                owner.clone(),
+                Some(10),
+            )
+            .await
+            .unwrap();
+        let orders = stream.next().await.unwrap().orders;
+        assert_eq!(orders[0].side, BloxrouteOpenbookSide::Ask);
+        assert_eq!(orders[0].clientOrderID, "42");
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["params"]["address"], owner);
+        assert_eq!(sent["params"]["limit"], "10");
+
+        let mut stream = client
+            .subscribe_to_openbook_get_unsettled(
+                "unsettledId".to_string(),
+                "SOL/USDC".to_string(),
+                owner,
+            )
+            .await
+            .unwrap();
+        let unsettled = stream.next().await.unwrap();
+        assert_eq!(unsettled.unsettled[0].quoteTokenAmount, 15.05);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn gets_openbook_open_orders_and_unsettled_over_http() {
+        let owner = "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string();
+        let (endpoint, mut requests) = spawn_recording_http_server(
+            r#"{"orders":[{"orderID":"2770756446290057010245906","market":"SOLUSDC","side":"S_BID","types":["OT_LIMIT"],"price":149.5,"remainingSize":0.2,"createdAt":null,"clientOrderID":"7","openOrderAccount":"4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN"}]}"#,
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+
+        let orders = client
+            .get_openbook_open_orders("SOLUSDC".to_string(), owner.clone(), Some(10))
+            .await
+            .unwrap();
+        let (request_line, _) = requests.recv().await.unwrap();
+        assert_eq!(
+            request_line,
+            "GET /api/v2/openbook/open-orders/SOLUSDC?address=AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ&limit=10 HTTP/1.1"
+        );
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].orderID, "2770756446290057010245906");
+        assert_eq!(orders[0].side, BloxrouteOpenbookSide::Bid);
+        assert_eq!(orders[0].types, vec!["OT_LIMIT"]);
+        assert_eq!(orders[0].remainingSize, 0.2);
+        assert_eq!(orders[0].clientOrderID, "7");
+
+        client
+            .get_openbook_open_orders("SOLUSDC".to_string(), owner.clone(), None)
+            .await
+            .unwrap();
+        let (request_line, _) = requests.recv().await.unwrap();
+        assert_eq!(
+            request_line,
+            "GET /api/v2/openbook/open-orders/SOLUSDC?address=AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ HTTP/1.1"
+        );
+
+        let (endpoint, mut requests) = spawn_recording_http_server(
+            r#"{"market":"SOLUSDC","unsettled":[{"account":"4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN","baseToken":"So11111111111111111111111111111111111111112","quoteToken":"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v","baseTokenAmount":0.5,"quoteTokenAmount":15.05}]}"#,
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let unsettled = client
+            .get_openbook_unsettled("SOLUSDC".to_string(), owner)
+            .await
+            .unwrap();
+        let (request_line, _) = requests.recv().await.unwrap();
+        assert_eq!(
+            request_line,
+            "GET /api/v2/openbook/unsettled/SOLUSDC?ownerAddress=AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ HTTP/1.1"
+        );
+        assert_eq!(unsettled.market, "SOLUSDC");
+        let account = &unsettled.unsettled[0];
+        assert_eq!(
+            account.account,
+            "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN"
+        );
+        assert_eq!(account.baseTokenAmount, 0.5);
+        assert_eq!(account.quoteTokenAmount, 15.05);
+    }
+
+    #[tokio::test]
+    async fn streams_openbook_orderbooks_and_tickers() {
+        let (endpoint, mut requests) = spawn_server(|request| match request["id"].as_str() {
+            Some("orderbooksId") => vec![
+                SUBSCRIPTION_ACK.replace("newTxsId", "orderbooksId"),
+                OPENBOOK_ORDERBOOKS_STREAM.to_string(),
+            ],
+            Some("tickersId") => vec![
+                r#"{"id":"tickersId","result":"8c2a1f4e-3b7d-4e59-9a61-2f0d5c7e9b13","jsonrpc":"2.0"}"#.to_string(),
+                OPENBOOK_TICKERS_STREAM.to_string(),
+            ],
+            _ => vec![],
+        })
+        .await;
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let markets = vec!["SOL/USDC".to_string(), "RAY/USDC".to_string()];
+
+        let payload = BloxrouteOpenbookOrderbooksStreamPayload {
+            markets: markets.clone(),
+            limit: Some(5),
+            project: None,
+        };
+        let mut orderbooks = client
+            .subscribe_to_openbook_orderbooks_stream("orderbooksId".to_string(), payload)
+            .await
+            .unwrap();
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(
+            sent["params"],
+            json!(["GetOrderbooksStream", {"markets": ["SOL/USDC", "RAY/USDC"], "limit": 5}])
+        );
+
+        let mut tickers = client
+            .subscribe_to_openbook_tickers_stream("tickersId".to_string(), markets)
+            .await
+            .unwrap();
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["params"][0], "GetTickersStream");
+
+        let update = orderbooks.next().await.unwrap();
+        assert_eq!(update.slot, 250114623);
+        assert_eq!(update.orderbook.bids[0].price, 149.81);
+        assert_eq!(update.orderbook.asks[0].clientOrderID, "42");
+        let update = tickers.next().await.unwrap();
+        assert_eq!(update.slot, 250114624);
+        assert_eq!(update.ticker.ask, 149.86);
+    }
+
+    #[test]
+    fn maintains_local_order_book() {
+        let depth: BloxrouteOpenbookGetDepthResponse = serde_json::from_value(json!({
+            "market": "SOL/USDC",
+            "marketAddress": "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6",
+            "bids": [{"price": 149.8, "size": 4.0}, {"price": 149.8, "size": 1.0}],
+            "asks": [{"price": 149.9, "size": 2.0}, {"price": 150.0, "size": 0.0}]
+        }))
+        .unwrap();
+        let update: BloxrouteGeneric<BloxrouteOpenbookOrderbookStreamResponse> =
+            serde_json::from_str(OPENBOOK_ORDERBOOKS_STREAM).unwrap();
+        let update = update.params.result;
+
+        let mut book = BloxrouteOrderBook::new("SOL/USDC");
+        assert_eq!(
+            book.apply_update(&update).unwrap(),
+            BloxrouteOrderBookUpdate::Gap
+        );
+
+        book.snapshot_depth(&depth, Some(250114600));
+        assert_eq!(book.size_at(BloxrouteOpenbookSide::Bid, 149.8), 5.0);
+        assert_eq!(book.levels(BloxrouteOpenbookSide::Ask), vec![(149.9, 2.0)]);
+        assert_eq!(book.vwap(BloxrouteOpenbookSide::Bid, 3.0), None);
+
+        assert_eq!(
+            book.apply_update(&update).unwrap(),
+            BloxrouteOrderBookUpdate::Applied
+        );
+        assert_eq!(book.slot(), Some(250114623));
+        assert_eq!(book.best_bid(), Some((149.81, 12.5)));
+        assert_eq!(book.best_ask(), Some((149.86, 3.1)));
+        assert!((book.mid().unwrap() - 149.835).abs() < 1e-9);
+        // A second book in the same slot is as current as the first.
+        assert_eq!(
+            book.apply_depth(&depth, 250114623).unwrap(),
+            BloxrouteOrderBookUpdate::Applied
+        );
+        assert_eq!(book.best_ask(), Some((149.9, 2.0)));
+        assert_eq!(
+            book.apply_update(&update).unwrap(),
+            BloxrouteOrderBookUpdate::Applied
+        );
+        assert_eq!(
+            book.apply_depth(&depth, 250114622).unwrap(),
+            BloxrouteOrderBookUpdate::Stale
+        );
+        assert_eq!(book.best_ask(), Some((149.86, 3.1)));
+
+        // Full books are applied however quiet the market was in between.
+        let mut book = book.with_max_slot_gap(150);
+        assert_eq!(
+            book.apply_depth(&depth, 250114623 + 100_000).unwrap(),
+            BloxrouteOrderBookUpdate::Applied
+        );
+        assert_eq!(book.best_ask(), Some((149.9, 2.0)));
+
+        // Level changes are merged into the book, as long as none were skipped.
+        assert_eq!(
+            book.apply_deltas([(149.8, 0.0), (149.7, 3.0)], [(149.95, 1.5)], 250214633),
+            BloxrouteOrderBookUpdate::Applied
+        );
+        assert_eq!(book.best_bid(), Some((149.7, 3.0)));
+        assert_eq!(
+            book.levels(BloxrouteOpenbookSide::Ask),
+            vec![(149.9, 2.0), (149.95, 1.5)]
+        );
+        assert_eq!(
+            book.apply_deltas([(149.7, 1.0)], [], 250214633 - 1),
+            BloxrouteOrderBookUpdate::Stale
+        );
+        assert_eq!(
+            book.apply_deltas([(149.7, 1.0)], [], 250214633 + 151),
+            BloxrouteOrderBookUpdate::Gap
+        );
+        assert!(book.needs_snapshot());
+        assert_eq!(
+            book.apply_deltas([(149.7, 1.0)], [], 250214633 + 152),
+            BloxrouteOrderBookUpdate::Gap
+        );
+        book.snapshot_depth(&depth, Some(250214633 + 152));
+        assert_eq!(
+            book.apply_update(&update).unwrap(),
+            BloxrouteOrderBookUpdate::Stale
+        );
+
+        let other = BloxrouteOrderBook::new("RAY/USDC").apply_depth(&depth, 1);
+        assert!(matches!(other, Err(BloxrouteError::OrderBook(_))));
+    }
+
+    #[tokio::test]
+    async fn returns_error_responses_from_subscribe() {
+        let (endpoint, mut requests) = spawn_server(|request| match request["id"].as_str() {
+            Some("newTxsId") => vec![SUBSCRIPTION_ACK.to_string(), TX_TYPE_1559_1.to_string()],
+            Some(id) => vec![json!({
+                "id": id,
+                "error": {"code": -32602, "message": "Invalid params", "data": "invalid filters"},
+                "jsonrpc": "2.0"
+            })
+            .to_string()],
+            None => vec![],
+        })
+        .await;
+
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut params = new_txs_params();
+        params.filters = Some(BloxrouteFilter::raw("{to} = 0x"));
+        match client
+            .subscribe_to_pending_txs("pendingTxsId".to_string(), params)
+            .await
+        {
+            Err(BloxrouteError::JsonRpc { code, data, .. }) => {
+                assert_eq!(code, -32602);
+                assert_eq!(data, Some(json!("invalid filters")));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        // Nothing is left to unsubscribe from.
+        requests.recv().await.unwrap();
+        assert!(!client.unsubscribe("pendingTxsId").await.unwrap());
+        assert!(client.get_subscription_id("pendingTxsId").await.is_none());
+        assert!(requests.try_recv().is_err());
+
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        assert!(stream.next().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn subscribe_times_out_without_confirmation() {
+        let (endpoint, mut requests) = spawn_server(|_| vec![]).await;
+
+        let mut client = BloxrouteClientBuilder::new(endpoint, "auth")
+            .request_timeout(Duration::from_millis(50))
+            .connect_ws()
+            .await
+            .unwrap();
+        let result = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await;
+        assert!(matches!(result, Err(BloxrouteError::Timeout)));
+        requests.recv().await.unwrap();
+        drop(result);
+
+        // The timed out subscription is forgotten rather than left to be acknowledged.
+        assert!(!client.unsubscribe("newTxsId").await.unwrap());
+        assert!(client.get_subscription_id("newTxsId").await.is_none());
+        assert!(requests.try_recv().is_err());
+    }
+
+    // The handshake callback's error type is dictated by tungstenite.
+    #[allow(clippy::result_large_err)]
+    #[tokio::test]
+    async fn connects_through_proxy_with_configured_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+
+        // Plays both the proxy and the gateway behind it.
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut connect = Vec::new();
+            while !connect.ends_with(b"\r\n\r\n") {
+                connect.push(stream.read_u8().await.unwrap());
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                .await
+                .unwrap();
+            let mut user_agent = None;
+            let _ws = accept_hdr_async(stream, |request: &Request, response: Response| {
+                user_agent = request.headers().get("User-Agent").cloned();
+                Ok(response)
+            })
+            .await
+            .unwrap();
+            (String::from_utf8(connect).unwrap(), user_agent)
+        });
+
+        let _client = BloxrouteClientBuilder::new("ws://gateway.invalid:8080/ws", "auth")
+            .proxy(proxy)
+            .user_agent("bloxroute-test")
+            .connect_ws()
+            .await
+            .unwrap();
+
+        let (connect, user_agent) = server.await.unwrap();
+        assert!(connect.starts_with("CONNECT gateway.invalid:8080 HTTP/1.1"));
+        assert_eq!(user_agent.unwrap(), "bloxroute-test");
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let policy = BloxrouteReconnectPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            max_attempts: Some(2),
+        };
+        let result = BloxrouteClientBuilder::new(endpoint, "auth")
+            .reconnect_policy(policy)
+            .connect_ws()
+            .await;
+        assert!(matches!(result, Err(BloxrouteError::WebSocket(_))));
+    }
+
+    #[test]
+    fn endpoint_urls() {
+        assert_eq!(
+            BloxrouteEndpoint::Ethereum(BloxrouteEvmRegion::Germany).ws_url(),
+            "wss://germany.eth.blxrbdn.com/ws"
+        );
+        assert_eq!(BloxrouteEndpoint::solana().len(), 6);
+        assert!(BloxrouteEndpoint::bsc()
+            .iter()
+            .all(|endpoint| endpoint.ws_url().ends_with(".bsc.blxrbdn.com/ws")));
+    }
+
+    #[tokio::test]
+    async fn selects_fastest_endpoint() {
+        let (fast, _requests) = spawn_server(|_| vec![]).await;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let slow = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let _ws = accept_async(stream).await;
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let unreachable = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let candidates = [
+            BloxrouteEndpoint::Custom(slow),
+            BloxrouteEndpoint::Custom(unreachable),
+            BloxrouteEndpoint::Custom(fast.clone()),
+        ];
+        let config = BloxrouteClientBuilder::new("", "auth");
+        let fastest = select_fastest_endpoint(&candidates, &config).await.unwrap();
+        assert_eq!(fastest.endpoint, BloxrouteEndpoint::Custom(fast));
+    }
+
+    #[tokio::test]
+    async fn merges_and_deduplicates_endpoints() {
+        let (first, _requests) = spawn_server(|_| {
+            vec![
+                SUBSCRIPTION_ACK.to_string(),
+                TX_TYPE_1559_1.to_string(),
+                // Same feed, so same subscription id as the first tx.
+                TX_TYPE_1559_2.replace(
+                    "aa9536b2-30c0-41bb-b9bb-950a6442a22b",
+                    "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+                ),
+            ]
+        })
+        .await;
+        let (second, _requests) =
+            spawn_server(|_| vec![SUBSCRIPTION_ACK.to_string(), TX_TYPE_1559_1.to_string()]).await;
+
+        let mut client = BloxrouteMultiWsClient::connect(vec![
+            BloxrouteClientBuilder::new(first, "auth"),
+            BloxrouteClientBuilder::new(second, "auth"),
+        ])
+        .await
+        .unwrap();
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+
+        let items = vec![stream.next().await.unwrap(), stream.next().await.unwrap()];
+        let mut hashes: Vec<_> = items.iter().map(|item| item.item.txHash).collect();
+        hashes.sort();
+        hashes.dedup();
+        assert_eq!(hashes.len(), 2);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+        // Only the transaction delivered by both endpoints has a lead.
+        let shared = b256!("0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e");
+        for item in &items {
+            assert_eq!(item.lead().is_some(), item.item.txHash == shared);
+        }
+
+        let stats = stream.stats();
+        assert_eq!(stats.iter().map(|stats| stats.wins).sum::<u64>(), 2);
+        assert_eq!(stats.iter().map(|stats| stats.losses).sum::<u64>(), 1);
+        assert_eq!(stats.iter().map(|stats| stats.outpaced).sum::<u64>(), 1);
+    }
+
+    #[tokio::test]
+    async fn tolerates_unreachable_and_rejecting_endpoints() {
+        let (accepting, _requests) =
+            spawn_server(|_| vec![SUBSCRIPTION_ACK.to_string(), TX_TYPE_1559_1.to_string()]).await;
+        let (rejecting, _requests) = spawn_server(|request| {
+            vec![json!({
+                "id": request["id"],
+                "error": {"code": -32602, "message": "Invalid params"},
+                "jsonrpc": "2.0"
+            })
+            .to_string()]
+        })
+        .await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let unreachable = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        // The unreachable endpoint would otherwise be retried forever.
+        let mut client = BloxrouteMultiWsClient::connect_with_deadline(
+            vec![
+                BloxrouteClientBuilder::new(unreachable, "auth"),
+                BloxrouteClientBuilder::new(rejecting, "auth"),
+                BloxrouteClientBuilder::new(accepting.clone(), "auth"),
+            ],
+            Duration::from_millis(200),
+        )
+        .await
+        .unwrap();
+        assert_eq!(client.clients.len(), 2);
+
+        let mut stream = client
+            .subscribe_to_new_txs("newTxsId".to_string(), new_txs_params())
+            .await
+            .unwrap();
+        let item = stream.next().await.unwrap();
+        assert_eq!(item.endpoint, accepting);
+        assert_eq!(item.endpoint_index, 1);
+    }
+
+    #[tokio::test]
+    async fn follows_transaction_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let (tx, mut requests) = mpsc::unbounded_channel();
+        // Drops the connection once the watched hashes were updated, and returns the
+        // subscription replayed on the next one.
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let frames = match request["method"].as_str() {
+                    Some("subscribe") => vec![
+                        SUBSCRIPTION_ACK.replace("newTxsId", "txStatusId"),
+                        TX_STATUS.to_string(),
+                    ],
+                    _ => vec![
+                        json!({"id": request["id"], "result": true, "jsonrpc": "2.0"}).to_string(),
+                    ],
+                };
+                for frame in frames {
+                    ws.send(Message::Text(frame)).await.unwrap();
+                }
+                let last = request["method"] == "stop_monitoring_tx";
+                let _ = tx.send(request);
+                if last {
+                    break;
+                }
+            }
+            drop(ws);
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            match ws.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    serde_json::from_str::<serde_json::Value>(&text).unwrap()
+                }
+                other => panic!("unexpected frame {:?}", other),
+            }
+        });
+
+        let client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let params = BloxrouteTransactionStatusRequest {
+            include: None,
+            transactions: vec![
+                "0x0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e".to_string(),
+            ],
+            blockchain_network: None,
+        };
+        let mut stream = client
+            .subscribe_to_transaction_status("txStatusId".to_string(), params)
+            .await
+            .unwrap();
+        let status = stream.next().await.unwrap();
+        assert_eq!(status.status, BloxrouteTransactionStatusKind::Mined);
+        assert_eq!(status.blockNumber, Some(0x13a5b7c));
+        requests.recv().await.unwrap();
+
+        assert!(client
+            .watch_transactions(&stream, vec!["0x01".to_string()])
+            .await
+            .unwrap());
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request["method"], "start_monitoring_tx");
+        assert_eq!(
+            request["params"]["subscription_id"],
+            "5d50c6c1-e752-4439-8dbf-0f9e4406761c"
+        );
+
+        assert!(client
+            .unwatch_transactions(
+                &stream,
+                vec![
+                    "0x0e449faf24bb05f1150117b354e1d7c94360d594755059ff35d7a40823db5a3e"
+                        .to_string()
+                ]
+            )
+            .await
+            .unwrap());
+        requests.recv().await.unwrap();
+
+        // The subscription is replayed with the hashes watched at the time.
+        let replayed = tokio::time::timeout(Duration::from_secs(10), server)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(replayed["id"], "txStatusId");
+        assert_eq!(replayed["params"][1]["transactions"], json!(["0x01"]));
+    }
+
+    #[tokio::test]
+    async fn runs_calls_on_every_block() {
+        let (endpoint, mut requests) = spawn_server(|_| {
+            let result = |name: &str, response: &str| {
+                json!({
+                    "method": "subscribe",
+                    "params": {
+                        "subscription": "5d50c6c1-e752-4439-8dbf-0f9e4406761c",
+                        "result": {"name": name, "response": response, "blockHeight": "20601724", "tag": "0x13a5b7c"}
+                    },
+                    "jsonrpc": "2.0"
+                })
+                .to_string()
+            };
+            vec![
+                SUBSCRIPTION_ACK.replace("newTxsId", "ethOnBlockId"),
+                result("height", "0x13a5b7c"),
+                result("balance", "0x1bc16d674ec80000"),
+                result("decimals", "0x0000000000000000000000000000000000000000000000000000000000000012"),
+                result("TaskCompletedEvent", ""),
+            ]
+        })
+        .await;
+
+        let client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let params = BloxrouteEthOnBlockRequest {
+            include: None,
+            call_params: vec![
+                BloxrouteEthOnBlockCall::BlockNumber {
+                    name: "height".to_string(),
+                },
+                BloxrouteEthOnBlockCall::GetBalance {
+                    name: "balance".to_string(),
+                    address: "0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430".to_string(),
+                    tag: BLOCK_HEIGHT_PLACEHOLDER.to_string(),
+                },
+                BloxrouteEthOnBlockCall::Call {
+                    name: "decimals".to_string(),
+                    to: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(),
+                    data: "0x313ce567".to_string(),
+                    from: None,
+                    tag: "latest".to_string(),
+                },
+            ],
+            blockchain_network: None,
+        };
+        let stream = client
+            .subscribe_to_eth_on_block("ethOnBlockId".to_string(), params)
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        let calls = &request["params"][1]["call-params"];
+        assert_eq!(
+            calls[0],
+            json!({"method": "eth_blockNumber", "name": "height"})
+        );
+        assert_eq!(calls[1]["method"], "eth_getBalance");
+        assert_eq!(calls[1]["tag"], "{BlockHeight}");
+
+        let mut blocks = Box::pin(stream.by_block());
+        let block = blocks.next().await.unwrap();
+        assert_eq!(block.blockHeight, 20601724);
+        assert_eq!(block.responses.len(), 3);
+        assert_eq!(block.get("height").unwrap().block_number(), Some(20601724));
+        assert_eq!(
+            block.get("balance").unwrap().balance(),
+            Some(uint!(2_000_000_000_000_000_000_U256))
+        );
+        let decimals = block.get("decimals").unwrap().call_output().unwrap();
+        assert_eq!(U256::from_be_slice(&decimals), U256::from(18));
+    }
+
+    /// Answers every HTTP request with the given raw response.
+    async fn spawn_http_server(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        endpoint
+    }
+
+    /// Answers every HTTP request with a 200 carrying `body`, and passes on the request line and
+    /// body of each request.
+    async fn spawn_recording_http_server(
+        body: &'static str,
+    ) -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![];
+                let mut buf = [0; 4096];
+                let head_len = loop {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                    if let Some(at) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break at + 4;
+                    }
+                };
+                let head = String::from_utf8_lossy(&request[..head_len]).to_string();
+                let content_length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                while request.len() < head_len + content_length {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let request_line = head.lines().next().unwrap().to_string();
+                let request_body = String::from_utf8_lossy(&request[head_len..]).to_string();
+                let _ = tx.send((request_line, request_body));
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (endpoint, rx)
+    }
+
+    #[tokio::test]
+    async fn http_errors_are_typed() {
+        let endpoint = spawn_http_server(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 2\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        match client.get_rate_limit().await {
+            Err(BloxrouteError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(2)))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let endpoint = spawn_http_server(
+            "HTTP/1.1 401 Unauthorized\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let err = client.get_rate_limit().await.unwrap_err();
+        assert!(matches!(err, BloxrouteError::Auth(_)));
+        assert!(!err.is_retryable());
+
+        let endpoint = spawn_http_server(
+            "HTTP/1.1 200 OK\r\nContent-Length: 11\r\nConnection: close\r\n\r\n{\"tier\": 1}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        match client.get_rate_limit().await {
+            Err(BloxrouteError::Deserialization { body, .. }) => assert_eq!(body, "{\"tier\": 1}"),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let (endpoint, _requests) =
+            spawn_recording_http_server("{\"id\":\"1\",\"jsonrpc\":\"2.0\"}").await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let request = BloxrouteSendTransactionRequest {
+            transaction: "f86b".to_string(),
+            ..Default::default()
+        };
+        match client.send_transaction(request).await {
+            Err(BloxrouteError::Deserialization { body, .. }) => {
+                assert_eq!(body, "{\"id\":\"1\",\"jsonrpc\":\"2.0\"}")
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn ws_connect_rejects_invalid_auth_header() {
+        let (endpoint, _requests) = spawn_server(|_| vec![]).await;
+        let result = BloxrouteWsClient::connect(endpoint, "bad\nheader".to_string(), 10).await;
+        assert!(matches!(result, Err(BloxrouteError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn sends_transactions() {
+        let (endpoint, mut requests) = spawn_server(|request| {
+            let result = match request["method"].as_str() {
+                // The gateway sends hashes without the `0x` prefix.
+                Some("blxr_tx") | Some("blxr_private_tx") => {
+                    json!({ "txHash": "6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4" })
+                }
+                Some("blxr_private_tx_cancel") => json!({ "status": "cancelled" }),
+                _ => json!({ "txHashes": ["6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4", "0xa3ece39ae137617669c6933b7578b94e705e765683f260fcfe30eaa41932610f"] }),
+            };
+            vec![json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string()]
+        })
+        .await;
+        let client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+
+        let request = BloxrouteSendTransactionRequest {
+            transaction: "f86b".to_string(),
+            nonce_monitoring: Some(true),
+            next_validator: Some(true),
+            fall_back: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            client.send_transaction(request).await.unwrap(),
+            b256!("6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4")
+        );
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["method"], "blxr_tx");
+        assert_eq!(
+            sent["params"],
+            json!({ "transaction": "f86b", "nonce_monitoring": true, "next_validator": true, "fall_back": 100 })
+        );
+
+        let request = BloxrouteSendBatchTransactionsRequest {
+            transactions: vec!["f86b".to_string(), "f86c".to_string()],
+            ..Default::default()
+        };
+        let hashes = client.send_batch_transactions(request).await.unwrap();
+        assert_eq!(
+            hashes,
+            vec![
+                b256!("6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4"),
+                b256!("a3ece39ae137617669c6933b7578b94e705e765683f260fcfe30eaa41932610f")
+            ]
+        );
+        requests.recv().await.unwrap();
+
+        let request = BloxrouteSendPrivateTransactionRequest {
+            transaction: "f86b".to_string(),
+            timeout: Some(30),
+            mev_builders: Some(HashMap::from([("all".to_string(), String::new())])),
+            front_running_protection: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            client.send_private_transaction(request).await.unwrap(),
+            b256!("6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4")
+        );
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["method"], "blxr_private_tx");
+        assert_eq!(sent["params"]["mev_builders"], json!({ "all": "" }));
+        assert_eq!(sent["params"]["front_running_protection"], true);
+
+        let request = BloxrouteCancelPrivateTransactionRequest {
+            transaction_hash: "6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4"
+                .to_string(),
+            ..Default::default()
+        };
+        client.cancel_private_transaction(request).await.unwrap();
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["method"], "blxr_private_tx_cancel");
+
+        let (endpoint, mut requests) = spawn_recording_http_server(
+            r#"{"jsonrpc":"2.0","id":"1","result":{"txHashes":["6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4","a3ece39ae137617669c6933b7578b94e705e765683f260fcfe30eaa41932610f"]}}"#,
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let request = BloxrouteSendBatchTransactionsRequest {
+            transactions: vec!["f86b".to_string(), "f86c".to_string()],
+            ..Default::default()
+        };
+        let hashes = client.send_batch_transactions(request).await.unwrap();
+        assert_eq!(
+            hashes,
+            vec![
+                b256!("6c1e6e2e5b2cd9a4b1b3bb1d27b2d0a0c0fb1b7a04e1e5f6c9f0f1c9a8e2d3b4"),
+                b256!("a3ece39ae137617669c6933b7578b94e705e765683f260fcfe30eaa41932610f")
+            ]
+        );
+        let (_, body) = requests.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap()["method"],
+            "blxr_batch_tx"
+        );
+
+        let endpoint = spawn_http_server(
+            "HTTP/1.1 200 OK\r\nContent-Length: 69\r\nConnection: close\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":\"1\",\"error\":{\"code\":-32602,\"message\":\"bad tx\"}}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let request = BloxrouteSendTransactionRequest {
+            transaction: "f86b".to_string(),
+            ..Default::default()
+        };
+        match client.send_transaction(request).await {
+            Err(BloxrouteError::JsonRpc { code, message, .. }) => {
+                assert_eq!((code, message.as_str()), (-32602, "bad tx"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn submits_and_simulates_bundles() {
+        let (endpoint, mut requests) = spawn_server(|request| match request["method"].as_str() {
+            Some("blxr_simulate_bundle") => {
+                vec![BUNDLE_SIMULATION.replace("\"id\": \"1\"", &format!("\"id\": {}", request["id"]))]
+            }
+            _ => vec![json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "bundleHash": "0xb00" } }).to_string()],
+        })
+        .await;
+        let client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+
+        let bundle = BloxrouteBundle {
+            transaction: vec!["f86b".to_string(), "f86c".to_string()],
+            block_number: "0x4fabd2".to_string(),
+            reverting_hashes: Some(vec![]),
+            uuid: Some("e1a8a3d4-3e49-4b34-a1a4-d6a4c7b0b6a5".to_string()),
+            builders: Some(vec!["all".to_string()]),
+            ..Default::default()
+        };
+        let simulation = client
+            .simulate_bundle(BloxrouteSimulateBundleRequest::from(&bundle))
+            .await
+            .unwrap();
+        assert_eq!(simulation.totalGasUsed, 51000);
+        let reverted: Vec<_> = simulation.reverted().collect();
+        assert_eq!(reverted.len(), 1);
+        assert_eq!(
+            reverted[0].revert.as_deref(),
+            Some("transfer amount exceeds balance")
+        );
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["params"]["state_block_number"], "latest");
+
+        assert_eq!(client.submit_bundle(bundle).await.unwrap(), "0xb00");
+        let sent = requests.recv().await.unwrap();
+        assert_eq!(sent["method"], "blxr_submit_bundle");
+        assert_eq!(
+            sent["params"],
+            json!({
+                "transaction": ["f86b", "f86c"],
+                "block_number": "0x4fabd2",
+                "reverting_hashes": [],
+                "uuid": "e1a8a3d4-3e49-4b34-a1a4-d6a4c7b0b6a5",
+                "builders": ["all"]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_and_validates_filters() {
+        use BloxrouteFilterField::{ChainId, From, GasPrice, MethodId, To, Value};
+
+        let router = address!("7a250d5630b4cf539739df2c5dacb4c659f2488d");
+        let filter = To
+            .eq(router)
+            .and(Value.gt(1_000_000_000_000_000_000u64))
+            .and(ChainId.eq(1u64));
+        assert_eq!(
+            filter.to_string(),
+            "({to} == '0x7a250d5630b4cf539739df2c5dacb4c659f2488d') AND ({value} > 1000000000000000000) AND ({chain_id} == 1)"
+        );
+        filter.validate().unwrap();
+
+        // OR nested in AND, and AND nested in OR, keep their own parentheses.
+        let filter = MethodId
+            .is_in(["a9059cbb", "0x095ea7b3"])
+            .or(From
+                .ne("0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430")
+                .and(GasPrice.le(30u64)))
+            .and(BloxrouteFilter::raw("{type} == 2"));
+        assert_eq!(
+            filter.to_string(),
+            "(({method_id} IN ['a9059cbb', '0x095ea7b3']) OR (({from} != '0x6c4c024d1c9d3f3a13fed0aa8930894e2cd31430') AND ({gas_price} <= 30))) AND ({type} == 2)"
+        );
+        filter.validate().unwrap();
+
+        assert_eq!(To.eq("0x' OR '1").to_string(), "({to} == '0x\\' OR \\'1')");
+        for invalid in [
+            To.eq("0x' OR '1"),
+            To.gt(router),
+            Value.eq("1e18"),
+            MethodId.eq("a9059c"),
+            From.is_in(Vec::<&str>::new()),
+            BloxrouteFilter::And(vec![]),
+        ] {
+            assert!(
+                matches!(invalid.validate(), Err(BloxrouteError::InvalidFilter(_))),
+                "{}",
+                invalid
+            );
+        }
+
+        // Rejected before anything is sent.
+        let (endpoint, mut requests) = spawn_server(|_| vec![]).await;
+        let mut client = BloxrouteWsClient::connect(endpoint, "auth".to_string(), 10)
+            .await
+            .unwrap();
+        let mut params = new_txs_params();
+        params.filters = Some(To.eq("0x"));
+        let result = client.subscribe_to_new_txs("1".to_string(), params).await;
+        assert!(matches!(result, Err(BloxrouteError::InvalidFilter(_))));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn gets_jupiter_quotes() {
+        let (endpoint, mut requests) = spawn_recording_http_server(JUPITER_QUOTE).await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let quote = client
+            .get_jupiter_quotes("SOL".to_string(), "USDC".to_string(), 0.1, 0.5)
+            .await
+            .unwrap();
+        let (request_line, _) = requests.recv().await.unwrap();
+        assert_eq!(
+            request_line,
+            "GET /api/v2/jupiter/quotes?inToken=SOL&outToken=USDC&inAmount=0.1&slippage=0.5 HTTP/1.1"
+        );
+        assert_eq!(
+            quote.outTokenAddress,
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+        );
+        let step = &quote.routes[0].steps[0];
+        assert_eq!(step.project.label, "Whirlpool");
+        assert_eq!(step.fee.amount, 0.00025);
+        assert_eq!(step.priceImpactPercent.infinity, "INF_NOT");
+        assert_eq!(quote.routes[0].outAmountMin, 14.97);
+    }
+
+    #[tokio::test]
+    async fn creates_jupiter_swap_transactions() {
+        let (endpoint, mut requests) = spawn_recording_http_server(JUPITER_SWAP).await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let payload = SwapPayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            inToken: "SOL".to_string(),
+            outToken: "USDC".to_string(),
+            inAmount: 0.1,
+            slippage: 0.5,
+            computeLimit: None,
+            computePrice: Some(1000),
+            tip: Some(1030),
+        };
+        let response = client
+            .create_jupiter_swap_transaction(payload)
+            .await
+            .unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/jupiter/swap HTTP/1.1");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            json!({
+                "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "inToken": "SOL",
+                "outToken": "USDC",
+                "inAmount": 0.1,
+                "slippage": 0.5,
+                "computeLimit": null,
+                "computePrice": 1000,
+                "tip": 1030
+            })
+        );
+        assert_eq!(
+            response.fees[0].mint,
+            "So11111111111111111111111111111111111111112"
+        );
+        assert_eq!(response.priceImpact.percent, 0.01);
+        assert_eq!(response.transactions[0].content, "AQAB");
+
+        let (endpoint, mut requests) = spawn_recording_http_server(JUPITER_ROUTE_SWAP).await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let payload = RouteSwapPayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            steps: vec![RouteStep {
+                inToken: "SOL".to_string(),
+                outToken: "USDC".to_string(),
+                inAmount: 0.1,
+                outAmount: 15.05,
+                outAmountMin: 14.97,
+                project: QuoteStepProject {
+                    label: "Whirlpool".to_string(),
+                    id: "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(),
+                },
+            }],
+            computeLimit: None,
+            computePrice: None,
+            tip: None,
+        };
+        let response = client.create_jupiter_route_swap(payload).await.unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/jupiter/route-swap HTTP/1.1");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            body["steps"],
+            json!([{
+                "inToken": "SOL",
+                "outToken": "USDC",
+                "inAmount": 0.1,
+                "outAmount": 15.05,
+                "outAmountMin": 14.97,
+                "project": {
+                    "label": "Whirlpool",
+                    "id": "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+                }
+            }])
+        );
+        assert!(response.priceImpact.is_none());
+        assert!(response.fees.is_none());
+        assert_eq!(response.transactions[0].content, "AQAC");
+    }
+
+    #[tokio::test]
+    async fn creates_jupiter_swap_instructions() {
+        let endpoint = spawn_http_server(
+            "HTTP/1.1 200 OK\r\nContent-Length: 314\r\nConnection: close\r\n\r\n{\"priceImpactPercent\":0.01,\"instructions\":[{\"programID\":\"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4\",\"accounts\":[{\"programKey\":\"AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ\",\"isSigner\":true,\"isWritable\":true}],\"data\":\"5RfLl3rjrSo=\"}],\"addressLookupTableAddresses\":[\"GxS6FiQ3mNnAar9HGQ6mxP7t6FcwmHkU7peSeQDUHmpN\"]}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let payload = SwapPayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            inToken: "SOL".to_string(),
+            outToken: "USDC".to_string(),
+            inAmount: 0.1,
+            slippage: 0.5,
+            computeLimit: None,
+            computePrice: Some(1000),
+            tip: None,
+        };
+        let response = client
+            .create_jupiter_swap_instructions(payload)
+            .await
+            .unwrap();
+        let instruction = &response.instructions[0];
+        assert_eq!(
+            instruction.programID,
+            "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+        );
+        assert!(instruction.accounts[0].isSigner);
+        assert_eq!(instruction.data, "5RfLl3rjrSo=");
+        assert_eq!(
+            response.addressLookupTableAddresses,
+            vec!["GxS6FiQ3mNnAar9HGQ6mxP7t6FcwmHkU7peSeQDUHmpN"]
+        );
+    }
+
+    #[tokio::test]
+    async fn creates_openbook_order_transactions() {
+        let order = BloxrouteOpenbookOrderPayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            payerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            market: "SOLUSDC".to_string(),
+            side: BloxrouteOpenbookSide::Ask,
+            r#type: BloxrouteOpenbookOrderType::PostOnly,
+            amount: 0.1,
+            price: 150.5,
+            openOrdersAddress: None,
+            clientOrderID: Some(42),
+            computeLimit: None,
+            computePrice: Some(1000),
+            tip: None,
+        };
+        let (endpoint, mut requests) = spawn_recording_http_server(
+            "{\"transaction\":{\"content\":\"AQAB\",\"isCleanup\":false},\"openOrdersAddress\":\"4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN\"}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let response = client.create_openbook_order(order.clone()).await.unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/openbook/place HTTP/1.1");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            json!({
+                "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "payerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "market": "SOLUSDC",
+                "side": "ask",
+                "type": "postonly",
+                "amount": 0.1,
+                "price": 150.5,
+                "openOrdersAddress": null,
+                "clientOrderID": 42,
+                "computeLimit": null,
+                "computePrice": 1000,
+                "tip": null
+            })
+        );
+        assert_eq!(response.transaction.content, "AQAB");
+        assert_eq!(
+            response.openOrdersAddress,
+            "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN"
+        );
+
+        let replace = BloxrouteOpenbookOrderPayload {
+            r#type: BloxrouteOpenbookOrderType::Limit,
+            price: 150.4,
+            openOrdersAddress: Some(response.openOrdersAddress),
+            ..order
+        };
+        let response = client.create_openbook_replace_order(replace).await.unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/openbook/replace HTTP/1.1");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            json!({
+                "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "payerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "market": "SOLUSDC",
+                "side": "ask",
+                "type": "limit",
+                "amount": 0.1,
+                "price": 150.4,
+                "openOrdersAddress": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN",
+                "clientOrderID": 42,
+                "computeLimit": null,
+                "computePrice": 1000,
+                "tip": null
+            })
+        );
+        assert_eq!(response.transaction.content, "AQAB");
+
+        let (endpoint, mut requests) = spawn_recording_http_server(
+            "{\"transactions\":[{\"content\":\"AQAB\",\"isCleanup\":false},{\"content\":\"AQAC\",\"isCleanup\":true}]}",
+        )
+        .await;
+        let client = BloxrouteHttpClient::new(endpoint, "auth".to_string());
+        let cancel = BloxrouteOpenbookCancelOrderPayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            marketAddress: "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6".to_string(),
+            openOrdersAddress: "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN".to_string(),
+            side: BloxrouteOpenbookSide::Ask,
+            order: BloxrouteOpenbookOrderRef::ClientOrderId(42),
+            computeLimit: None,
+            computePrice: None,
+            tip: None,
+        };
+        let transactions = client
+            .create_openbook_cancel_order(cancel.clone())
+            .await
+            .unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/openbook/cancel HTTP/1.1");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            json!({
+                "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "marketAddress": "8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6",
+                "openOrdersAddress": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN",
+                "side": "ask",
+                "clientOrderID": 42,
+                "computeLimit": null,
+                "computePrice": null,
+                "tip": null
+            })
+        );
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions[1].isCleanup);
+
+        let settle = BloxrouteOpenbookSettlePayload {
+            ownerAddress: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            market: "SOLUSDC".to_string(),
+            baseTokenWallet: "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ".to_string(),
+            quoteTokenWallet: "3wmyR7VdqYuhNFkEjGsbCtj3RZ3Qv5HNhqVgaWKdyRrA".to_string(),
+            openOrdersAddress: "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN".to_string(),
+            computeLimit: Some(200000),
+            computePrice: None,
+            tip: Some(1030),
+        };
+        let transactions = client.create_openbook_settle(settle).await.unwrap();
+        let (request_line, body) = requests.recv().await.unwrap();
+        assert_eq!(request_line, "POST /api/v2/openbook/settle HTTP/1.1");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            json!({
+                "ownerAddress": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "market": "SOLUSDC",
+                "baseTokenWallet": "AFT8VayE7qr8MoQsW3wHsDS83HhEvhGWdbNSHRKeUDfQ",
+                "quoteTokenWallet": "3wmyR7VdqYuhNFkEjGsbCtj3RZ3Qv5HNhqVgaWKdyRrA",
+                "openOrdersAddress": "4zrqJ3Mmx7TwShBYHkC4jZJd4ypVnFb1Fh5gfp2ZJyhN",
+                "computeLimit": 200000,
+                "computePrice": null,
+                "tip": 1030
+            })
+        );
+        assert_eq!(transactions[0].content, "AQAB");
+
+        let cancel = BloxrouteOpenbookCancelOrderPayload {
+            order: BloxrouteOpenbookOrderRef::OrderId("7".to_string()),
+            ..cancel
+        };
+        let payload = serde_json::to_value(&cancel).unwrap();
+        assert_eq!(payload["orderID"], "7");
+        assert!(payload.get("clientOrderID").is_none());
+        let decoded: BloxrouteOpenbookCancelOrderPayload = serde_json::from_value(payload).unwrap();
+        assert_eq!(decoded.order, cancel.order);
+        assert_eq!(
+            serde_json::from_str::<BloxrouteOpenbookOrderType>("\"OT_POST\"").unwrap(),
+            BloxrouteOpenbookOrderType::PostOnly
         );
     }
 }